@@ -109,6 +109,7 @@ mod feature {
         // Demonstration app state that we'll control with our conrod GUI.
         let mut app = support::DemoApp::new(rust_logo);
 
+        let mut last_update = std::time::Instant::now();
         'main: loop {
             // If the window is closed, this will be None for one tick, so to avoid panicking with
             // unwrap, instead break the loop
@@ -134,6 +135,11 @@ mod feature {
                 device.cleanup();
             }
 
+            // Advance the `Ui`'s frame clock, which times the tooltip delay.
+            let now = std::time::Instant::now();
+            ui.advance(support::secs(now.duration_since(last_update)));
+            last_update = now;
+
             let mut should_quit = false;
             events_loop.poll_events(|event|{
 
@@ -164,8 +170,8 @@ mod feature {
                 break 'main;
             }
 
-            // Update widgets if any event has happened
-            if ui.global_input().events().next().is_some() {
+            // Update widgets if any event has happened or a tooltip is waiting to be shown.
+            if ui.global_input().events().next().is_some() || ui.is_tooltip_pending() {
                 let mut ui = ui.set_widgets();
                 support::gui(&mut ui, &ids, &mut app);
             }
@@ -83,8 +83,14 @@ mod feature {
         let mut event_loop = support::EventLoop::new();
         'main: loop {
 
+            // Collect all pending events.
+            let events = event_loop.next(&mut events_loop);
+
+            // Advance the `Ui`'s frame clock, which times the tooltip delay.
+            ui.advance(event_loop.elapsed_secs());
+
             // Handle all events.
-            for event in event_loop.next(&mut events_loop) {
+            for event in events {
 
                 // Use the `winit` backend feature to convert the winit event to a conrod one.
                 if let Some(event) = conrod::backend::winit::convert_event(event.clone(), &display) {
@@ -112,6 +118,11 @@ mod feature {
             // Instantiate a GUI demonstrating every widget type provided by conrod.
             support::gui(&mut ui.set_widgets(), &ids, &mut app);
 
+            // Keep updating while the mouse rests over a widget whose tooltip is yet to be shown.
+            if ui.is_tooltip_pending() {
+                event_loop.needs_update();
+            }
+
             // Draw the `Ui`.
             if let Some(primitives) = ui.draw_if_changed() {
                 renderer.fill(&display, primitives, &image_map);
@@ -89,6 +89,7 @@ mod feature {
             // Many widgets require another frame to finish drawing after clicks or hovers, so we
             // insert an update into the conrod loop using this `bool` after each event.
             let mut needs_update = true;
+            let mut last_update = std::time::Instant::now();
             'conrod: loop {
 
                 // Collect any pending events.
@@ -97,16 +98,31 @@ mod feature {
                     events.push(event);
                 }
 
-                // If there are no events pending, wait for them.
+                // If there are no events pending, wait for them. While the mouse rests over a
+                // widget whose tooltip is yet to be shown, only wait until the next frame.
                 if events.is_empty() || !needs_update {
-                    match event_rx.recv() {
-                        Ok(event) => events.push(event),
-                        Err(_) => break 'conrod,
-                    };
+                    if ui.is_tooltip_pending() {
+                        let sixteen_ms = std::time::Duration::from_millis(16);
+                        match event_rx.recv_timeout(sixteen_ms) {
+                            Ok(event) => events.push(event),
+                            Err(std::sync::mpsc::RecvTimeoutError::Timeout) => (),
+                            Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => break 'conrod,
+                        };
+                    } else {
+                        match event_rx.recv() {
+                            Ok(event) => events.push(event),
+                            Err(_) => break 'conrod,
+                        };
+                    }
                 }
 
                 needs_update = false;
 
+                // Advance the `Ui`'s frame clock, which times the tooltip delay.
+                let now = std::time::Instant::now();
+                ui.advance(support::secs(now.duration_since(last_update)));
+                last_update = now;
+
                 // Input each event into the `Ui`.
                 for event in events {
                     ui.handle_event(event);
@@ -453,6 +453,7 @@ mod feature {
                 match event {
                     widget::text_box::Event::Enter => println!("TextBox {}: {:?}", i, text),
                     widget::text_box::Event::Update(string) => *text = string,
+                    widget::text_box::Event::Complete(_) => (),
                 }
             }
 
@@ -67,6 +67,9 @@ pub fn theme() -> conrod::Theme {
         widget_styling: conrod::theme::StyleMap::default(),
         mouse_drag_threshold: 0.0,
         double_click_threshold: std::time::Duration::from_millis(500),
+        focus_color: conrod::color::LIGHT_BLUE,
+        focus_width: 2.0,
+        tooltip_delay: std::time::Duration::from_millis(600),
     }
 }
 
@@ -270,6 +273,7 @@ pub fn gui(ui: &mut conrod::UiCell, ids: &Ids, app: &mut DemoApp) {
         .mid_left_with_margin_on(ids.canvas, MARGIN)
         .down_from(ids.button_title, 60.0)
         .w_h(side, side)
+        .tooltip("Move the ball to a random position")
         .set(ids.button, ui)
     {
         let x = rand::random::<conrod::Scalar>() * (max_x - min_x) - max_x;
@@ -357,6 +361,12 @@ pub fn gui(ui: &mut conrod::UiCell, ids: &Ids, app: &mut DemoApp) {
 }
 
 
+/// The given `Duration` in seconds, as used to advance the `Ui`'s frame clock.
+pub fn secs(duration: std::time::Duration) -> f64 {
+    duration.as_secs() as f64 + duration.subsec_nanos() as f64 / 1_000_000_000.0
+}
+
+
 /// In most of the examples the `glutin` crate is used for providing the window context and
 /// events while the `glium` crate is used for displaying `conrod::render::Primitives` to the
 /// screen.
@@ -367,6 +377,7 @@ pub fn gui(ui: &mut conrod::UiCell, ids: &Ids, app: &mut DemoApp) {
 pub struct EventLoop {
     ui_needs_update: bool,
     last_update: std::time::Instant,
+    elapsed_secs: f64,
 }
 
 #[cfg(feature="glium")]
@@ -376,6 +387,7 @@ impl EventLoop {
         EventLoop {
             last_update: std::time::Instant::now(),
             ui_needs_update: true,
+            elapsed_secs: 0.0,
         }
     }
 
@@ -403,11 +415,20 @@ impl EventLoop {
         }
 
         self.ui_needs_update = false;
-        self.last_update = std::time::Instant::now();
+        let now = std::time::Instant::now();
+        self.elapsed_secs = secs(now.duration_since(self.last_update));
+        self.last_update = now;
 
         events
     }
 
+    /// The number of seconds between the two most recent calls to `next`.
+    ///
+    /// This is used to advance the `Ui`'s frame clock, which times the tooltip delay.
+    pub fn elapsed_secs(&self) -> f64 {
+        self.elapsed_secs
+    }
+
     /// Notifies the event loop that the `Ui` requires another update whether or not there are any
     /// pending events.
     ///
@@ -115,7 +115,7 @@ mod feature {
             .color(color::DARK_CHARCOAL)
             .set(ids.canvas, ui);
 
-        for edit in widget::TextEdit::new(demo_text)
+        for event in widget::TextEdit::new(demo_text)
             .color(color::WHITE)
             .padded_w_of(ids.canvas, 20.0)
             .mid_top_of(ids.canvas)
@@ -124,7 +124,9 @@ mod feature {
             .restrict_to_height(false) // Let the height grow infinitely and scroll.
             .set(ids.text_edit, ui)
         {
-            *demo_text = edit;
+            if let widget::text_edit::Event::Update(edit) = event {
+                *demo_text = edit;
+            }
         }
 
         widget::Scrollbar::y_axis(ids.canvas).auto_hide(true).set(ids.scrollbar, ui);
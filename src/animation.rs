@@ -0,0 +1,179 @@
+//! Interpolation of values over time, driven by the `Ui`'s frame clock.
+//!
+//! Values are animated via `UiCell::animate`, while the clock is advanced via `Ui::advance` or by
+//! passing `event::Input::Update` events to `Ui::handle_event`.
+
+use color::{Color, Rgba};
+use fnv;
+use position::{Point, Scalar};
+use widget;
+
+/// Describes how an animated value progresses from its start to its target over time.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Easing {
+    /// Progress at a constant rate.
+    Linear,
+    /// Begin slowly and accelerate towards the target.
+    EaseIn,
+    /// Begin quickly and decelerate towards the target.
+    EaseOut,
+    /// Accelerate away from the start and decelerate towards the target.
+    EaseInOut,
+}
+
+/// Values that may be animated.
+///
+/// A value is interpolated by interpolating each of its components independently.
+pub trait Tween: Copy {
+    /// The components of the value.
+    fn to_components(self) -> [Scalar; 4];
+    /// Produce a value from the given components.
+    fn from_components(components: [Scalar; 4]) -> Self;
+}
+
+/// The animations in progress for each widget, keyed by the widget and a name unique to that
+/// widget.
+#[derive(Clone, Debug, Default)]
+pub struct Animations {
+    map: fnv::FnvHashMap<(widget::Id, &'static str), Animation>,
+}
+
+/// A single value animating towards its target.
+#[derive(Copy, Clone, Debug, PartialEq)]
+struct Animation {
+    start: [Scalar; 4],
+    target: [Scalar; 4],
+    duration: Scalar,
+    elapsed: Scalar,
+    easing: Easing,
+}
+
+impl Easing {
+
+    /// The progress towards the target at the given fraction `t` of the duration, where `t` is
+    /// between `0.0` and `1.0`.
+    pub fn apply(self, t: Scalar) -> Scalar {
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t * t,
+            Easing::EaseOut => {
+                let t = 1.0 - t;
+                1.0 - t * t * t
+            },
+            Easing::EaseInOut => if t < 0.5 {
+                4.0 * t * t * t
+            } else {
+                let t = 2.0 - 2.0 * t;
+                1.0 - t * t * t / 2.0
+            },
+        }
+    }
+
+}
+
+impl Animation {
+
+    /// Whether or not the value has yet to reach its target.
+    fn is_running(&self) -> bool {
+        self.elapsed < self.duration
+    }
+
+    /// The current components of the animated value.
+    fn components(&self) -> [Scalar; 4] {
+        if !self.is_running() {
+            return self.target;
+        }
+        let progress = self.easing.apply(self.elapsed / self.duration);
+        let mut components = self.start;
+        for (component, &target) in components.iter_mut().zip(&self.target) {
+            *component += (target - *component) * progress;
+        }
+        components
+    }
+
+}
+
+impl Animations {
+
+    /// Produce the current value of the animation with the given key, first beginning a new
+    /// animation from the current value if the `target` has changed.
+    ///
+    /// The first time a key is animated, the value begins at its `target`.
+    pub fn animate<T>(&mut self,
+                      widget: widget::Id,
+                      key: &'static str,
+                      target: T,
+                      duration: Scalar,
+                      easing: Easing) -> T
+        where T: Tween,
+    {
+        let target = target.to_components();
+        let animation = self.map.entry((widget, key)).or_insert(Animation {
+            start: target,
+            target: target,
+            duration: 0.0,
+            elapsed: 0.0,
+            easing: easing,
+        });
+        if animation.target != target {
+            *animation = Animation {
+                start: animation.components(),
+                target: target,
+                duration: duration,
+                elapsed: 0.0,
+                easing: easing,
+            };
+        }
+        T::from_components(animation.components())
+    }
+
+    /// Advance all animations by the given number of seconds.
+    pub fn advance(&mut self, secs: Scalar) {
+        for animation in self.map.values_mut() {
+            if animation.is_running() {
+                animation.elapsed = (animation.elapsed + secs).min(animation.duration);
+            }
+        }
+    }
+
+    /// Whether or not any value has yet to reach its target.
+    pub fn is_running(&self) -> bool {
+        self.map.values().any(Animation::is_running)
+    }
+
+    /// Remove the animations of all widgets for which the given function returns `false`.
+    pub fn retain<F>(&mut self, mut f: F)
+        where F: FnMut(widget::Id) -> bool,
+    {
+        self.map.retain(|&(widget, _), _| f(widget));
+    }
+
+}
+
+impl Tween for Scalar {
+    fn to_components(self) -> [Scalar; 4] {
+        [self, 0.0, 0.0, 0.0]
+    }
+    fn from_components(components: [Scalar; 4]) -> Self {
+        components[0]
+    }
+}
+
+impl Tween for Point {
+    fn to_components(self) -> [Scalar; 4] {
+        [self[0], self[1], 0.0, 0.0]
+    }
+    fn from_components(components: [Scalar; 4]) -> Self {
+        [components[0], components[1]]
+    }
+}
+
+impl Tween for Color {
+    fn to_components(self) -> [Scalar; 4] {
+        let Rgba(r, g, b, a) = self.to_rgb();
+        [r as Scalar, g as Scalar, b as Scalar, a as Scalar]
+    }
+    fn from_components(c: [Scalar; 4]) -> Self {
+        Color::Rgba(c[0] as f32, c[1] as f32, c[2] as f32, c[3] as f32)
+    }
+}
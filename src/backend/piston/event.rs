@@ -67,5 +67,9 @@ pub fn convert<E>(event: E, win_w: Scalar, win_h: Scalar) -> Option<Input>
         return Some(Input::Focus(b));
     }
 
+    if let Some(args) = event.update_args() {
+        return Some(Input::Update(args.dt));
+    }
+
     None
 }
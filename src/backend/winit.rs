@@ -3,6 +3,7 @@
 extern crate winit;
 
 use Scalar;
+use clipboard;
 use event::Input;
 use input;
 use cursor;
@@ -315,3 +316,48 @@ pub fn convert_mouse_cursor(cursor: cursor::MouseCursor) -> winit::MouseCursor {
         _ => winit::MouseCursor::Arrow,
     }
 }
+
+/// A `conrod::clipboard::Clipboard` that forwards to the given functions.
+///
+/// `winit` does not provide access to the system clipboard, so this allows an application to
+/// install whichever platform clipboard it uses (e.g. via the `clipboard` crate) on the `Ui`:
+///
+/// ```ignore
+/// let mut get_ctx: ClipboardContext = ClipboardProvider::new().unwrap();
+/// let mut set_ctx: ClipboardContext = ClipboardProvider::new().unwrap();
+/// let clipboard = conrod::backend::winit::Clipboard::new(
+///     move || get_ctx.get_contents().ok(),
+///     move |contents| { let _ = set_ctx.set_contents(contents); },
+/// );
+/// ui.set_clipboard(clipboard);
+/// ```
+pub struct Clipboard<G, S> {
+    get_contents: G,
+    set_contents: S,
+}
+
+impl<G, S> Clipboard<G, S>
+    where G: FnMut() -> Option<String> + Send,
+          S: FnMut(String) + Send,
+{
+    /// A clipboard that retrieves its contents via `get_contents` and replaces them via
+    /// `set_contents`.
+    pub fn new(get_contents: G, set_contents: S) -> Self {
+        Clipboard {
+            get_contents: get_contents,
+            set_contents: set_contents,
+        }
+    }
+}
+
+impl<G, S> clipboard::Clipboard for Clipboard<G, S>
+    where G: FnMut() -> Option<String> + Send,
+          S: FnMut(String) + Send,
+{
+    fn get_contents(&mut self) -> Option<String> {
+        (self.get_contents)()
+    }
+    fn set_contents(&mut self, contents: String) {
+        (self.set_contents)(contents)
+    }
+}
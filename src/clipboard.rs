@@ -0,0 +1,38 @@
+//! The `Clipboard` trait through which widgets copy and paste text, along with an in-memory
+//! implementation.
+
+/// A clipboard into which widgets may copy text and from which they may paste text.
+///
+/// The `Ui` uses a `Memory` clipboard by default, which is only shared between the widgets of a
+/// single `Ui`. Applications should install a clipboard that forwards to the platform's clipboard
+/// via `Ui::set_clipboard`.
+pub trait Clipboard: Send {
+    /// The current contents of the clipboard, or `None` if it contains no text.
+    fn get_contents(&mut self) -> Option<String>;
+    /// Replace the contents of the clipboard with the given text.
+    fn set_contents(&mut self, contents: String);
+}
+
+/// A `Clipboard` that stores its contents in memory.
+///
+/// This is the default clipboard used by the `Ui` and is useful for testing.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Memory {
+    contents: Option<String>,
+}
+
+impl Memory {
+    /// An empty in-memory clipboard.
+    pub fn new() -> Self {
+        Memory::default()
+    }
+}
+
+impl Clipboard for Memory {
+    fn get_contents(&mut self) -> Option<String> {
+        self.contents.clone()
+    }
+    fn set_contents(&mut self, contents: String) {
+        self.contents = Some(contents);
+    }
+}
@@ -0,0 +1,94 @@
+//! Dragging payloads of any type from one widget and dropping them onto another.
+//!
+//! A widget begins a drag via `UiCell::start_drag`, usually upon receiving a left mouse `Drag`.
+//! While the left mouse button remains down, the widget under the cursor receives `DragEnter`,
+//! `DragOver` and `DragLeave` events and may accept the payload via `UiCell::accept_drop`. If the
+//! button is released over a widget that accepted the payload, that widget receives a `Drop`
+//! event and may take a look at the payload via `Ui::drag_and_drop`.
+
+use std::any::Any;
+use widget;
+
+/// A payload being dragged from one widget to another.
+pub struct Drag {
+    source: widget::Id,
+    payload: Box<Any + Send>,
+    preview: Option<widget::Id>,
+    target: Option<widget::Id>,
+    accepted: bool,
+    finished: bool,
+}
+
+impl Drag {
+
+    /// Begin dragging the given `payload` from the `source` widget.
+    pub fn new(source: widget::Id, payload: Box<Any + Send>, preview: Option<widget::Id>) -> Self {
+        Drag {
+            source: source,
+            payload: payload,
+            preview: preview,
+            target: None,
+            accepted: false,
+            finished: false,
+        }
+    }
+
+    /// The widget from which the payload is being dragged.
+    pub fn source(&self) -> widget::Id {
+        self.source
+    }
+
+    /// The payload, if it is of type `T`.
+    pub fn payload<T>(&self) -> Option<&T>
+        where T: Any,
+    {
+        self.payload.downcast_ref()
+    }
+
+    /// The widget displayed beneath the cursor while dragging, if any.
+    ///
+    /// The preview and its children are never targeted by the drag.
+    pub fn preview(&self) -> Option<widget::Id> {
+        self.preview
+    }
+
+    /// The widget under the cursor that is being offered the payload, if any.
+    pub fn target(&self) -> Option<widget::Id> {
+        self.target
+    }
+
+    /// Whether or not the `target` has accepted the payload.
+    pub fn is_accepted(&self) -> bool {
+        self.accepted
+    }
+
+    /// Whether or not the payload has been dropped or the drag cancelled.
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Offer the payload to the given target, returning the previous target if it has changed.
+    pub fn set_target(&mut self, target: Option<widget::Id>) -> Option<Option<widget::Id>> {
+        if self.target == target {
+            return None;
+        }
+        self.accepted = false;
+        Some(::std::mem::replace(&mut self.target, target))
+    }
+
+    /// Accept or reject the payload on behalf of the given target.
+    ///
+    /// This has no effect if the given widget is not the current `target`.
+    pub fn set_accepted(&mut self, target: widget::Id, accepted: bool) {
+        if self.target == Some(target) {
+            self.accepted = accepted;
+        }
+    }
+
+    /// Mark the drag as finished, producing the target that the payload was offered to.
+    pub fn finish(&mut self) -> Option<widget::Id> {
+        self.finished = true;
+        self.target
+    }
+
+}
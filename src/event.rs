@@ -37,6 +37,7 @@
 
 use input;
 use position::{Dimensions, Point};
+use std;
 use utils::vec2_sub;
 use widget;
 
@@ -70,10 +71,16 @@ pub enum Input {
     Touch(input::Touch),
     /// Text input was received, usually via the keyboard.
     Text(String),
+    /// The state of an input method editor's (IME) composition changed.
+    Ime(Ime),
     /// The window was focused or lost focus.
     Focus(bool),
     /// The backed requested to redraw.
     Redraw,
+    /// The given number of seconds have passed since the previous `Update`.
+    ///
+    /// This advances the `Ui`'s frame clock, progressing any animations.
+    Update(f64),
 }
 
 
@@ -93,6 +100,9 @@ pub enum Event {
 pub enum Ui {
     /// Entered text, along with the widget that was capturing the keyboard at the time.
     Text(Option<widget::Id>, Text),
+    /// An IME composition event, along with the widget that was capturing the keyboard at the
+    /// time.
+    Ime(Option<widget::Id>, Ime),
     /// Some button was pressed, along with the widget that was capturing the device whose button
     /// was pressed.
     Press(Option<widget::Id>, Press),
@@ -116,6 +126,18 @@ pub enum Ui {
     Tap(Option<widget::Id>, Tap),
     /// Represents a pointing device button being pressed and a subsequent movement of the mouse.
     Drag(Option<widget::Id>, Drag),
+    /// A drag that was under way was cancelled by the user pressing `Escape`, along with the
+    /// widget that was pressed at the beginning of the drag.
+    DragCancelled(Option<widget::Id>, DragCancelled),
+    /// A drag-and-drop payload was moved onto the given widget.
+    DragEnter(widget::Id, DragAndDrop),
+    /// A drag-and-drop payload was moved over the given widget.
+    DragOver(widget::Id, DragAndDrop),
+    /// A drag-and-drop payload was moved off the given widget, or the drag ended without the
+    /// payload being dropped onto it.
+    DragLeave(widget::Id, DragAndDrop),
+    /// A drag-and-drop payload was dropped onto the given widget after it accepted the payload.
+    Drop(widget::Id, DragAndDrop),
     /// A generic scroll event.
     ///
     /// `Scroll` does not necessarily have to get created by a mouse wheel, it could be generated
@@ -143,6 +165,8 @@ pub enum Ui {
 pub enum Widget {
     /// Entered text.
     Text(Text),
+    /// The state of an IME composition changed.
+    Ime(Ime),
     /// Represents all forms of motion input.
     Motion(Motion),
     /// Interaction with a touch screen.
@@ -161,6 +185,23 @@ pub enum Widget {
     Tap(Tap),
     /// Represents a pointing device button being pressed and a subsequent movement of the mouse.
     Drag(Drag),
+    /// A drag that began on this widget was cancelled, so any changes made over the course of the
+    /// drag should be undone.
+    DragCancelled(DragCancelled),
+    /// A drag-and-drop payload was moved onto this widget.
+    ///
+    /// The widget may accept the payload via `UiCell::accept_drop`.
+    DragEnter(DragAndDrop),
+    /// A drag-and-drop payload was moved over this widget.
+    DragOver(DragAndDrop),
+    /// A drag-and-drop payload was moved off this widget, or the drag ended without the payload
+    /// being dropped onto it.
+    DragLeave(DragAndDrop),
+    /// A drag-and-drop payload that this widget accepted was dropped onto it.
+    ///
+    /// The payload may be retrieved via `Ui::drag_and_drop` until the end of the current
+    /// `set_widgets` stage.
+    Drop(DragAndDrop),
     /// Represents the amount of scroll that has been applied to this widget.
     Scroll(Scroll),
     /// The window's dimensions were resized.
@@ -180,6 +221,32 @@ pub struct Text {
     pub modifiers: input::keyboard::ModifierKey,
 }
 
+/// The stages of composing text via an input method editor (IME).
+///
+/// While composing, the IME produces "pre-edit" text that should be displayed in place at the
+/// cursor but is not yet a part of the text being edited. Once the user is done, the composition
+/// is either committed or cancelled.
+#[derive(Clone, PartialEq, Debug)]
+pub enum Ime {
+    /// The pre-edit text of the current composition changed.
+    Preedit(Preedit),
+    /// The composition finished, producing the given text.
+    Commit(String),
+    /// The composition was cancelled and its pre-edit text should be discarded.
+    Cancel,
+}
+
+/// The text of an IME composition that is yet to be committed.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Preedit {
+    /// The text being composed.
+    pub text: String,
+    /// The byte range of the `text` covered by the IME's cursor, if it has one.
+    ///
+    /// The range is empty if the IME shows a caret rather than a highlighted range.
+    pub cursor: Option<std::ops::Range<usize>>,
+}
+
 /// Contains all relevant information for a Motion event.
 #[derive(Copy, Clone, PartialEq, Debug)]
 pub struct Motion {
@@ -279,6 +346,30 @@ pub struct Drag {
     pub modifiers: input::keyboard::ModifierKey,
 }
 
+/// Contains all the relevant information for a cancelled mouse drag.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct DragCancelled {
+    /// Which mouse button was being held during the drag.
+    pub button: input::MouseButton,
+    /// The point from which the cancelled drag began.
+    pub origin: Point,
+    /// The position of the mouse at the time the drag was cancelled.
+    pub xy: Point,
+    /// Which modifier keys were being held when the drag was cancelled.
+    pub modifiers: input::keyboard::ModifierKey,
+}
+
+/// Contains all the relevant information for a drag-and-drop event.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct DragAndDrop {
+    /// The widget from which the payload is being dragged.
+    pub source: widget::Id,
+    /// The position of the mouse.
+    pub xy: Point,
+    /// Which modifier keys were being held at the time.
+    pub modifiers: input::keyboard::ModifierKey,
+}
+
 /// Contains all the relevant information for a mouse click.
 #[derive(Copy, Clone, PartialEq, Debug)]
 pub struct Click {
@@ -464,6 +555,27 @@ impl Drag {
     }
 }
 
+impl DragCancelled {
+    /// Returns a copy of the DragCancelled relative to the given `xy`
+    pub fn relative_to(&self, xy: Point) -> DragCancelled {
+        DragCancelled {
+            origin: vec2_sub(self.origin, xy),
+            xy: vec2_sub(self.xy, xy),
+            ..*self
+        }
+    }
+}
+
+impl DragAndDrop {
+    /// Returns a copy of the DragAndDrop relative to the given `xy`
+    pub fn relative_to(&self, xy: Point) -> DragAndDrop {
+        DragAndDrop {
+            xy: vec2_sub(self.xy, xy),
+            ..*self
+        }
+    }
+}
+
 
 impl From<input::Motion> for Input {
     fn from(motion: input::Motion) -> Self {
@@ -495,6 +607,12 @@ impl From<Text> for Widget {
     }
 }
 
+impl From<Ime> for Widget {
+    fn from(ime: Ime) -> Self {
+        Widget::Ime(ime)
+    }
+}
+
 impl From<Motion> for Widget {
     fn from(motion: Motion) -> Self {
         Widget::Motion(motion)
@@ -548,3 +666,9 @@ impl From<Drag> for Widget {
         Widget::Drag(drag)
     }
 }
+
+impl From<DragCancelled> for Widget {
+    fn from(drag_cancelled: DragCancelled) -> Self {
+        Widget::DragCancelled(drag_cancelled)
+    }
+}
@@ -85,6 +85,27 @@ impl DepthOrder {
         }
     }
 
+    /// Move the widget at the given index, along with all of its depth children, above all other
+    /// widgets, preserving their order relative to one another.
+    ///
+    /// This is used for the overlay drawn over all other widgets, e.g. tooltips.
+    pub fn bring_to_front(&mut self, graph: &Graph, idx: widget::Id) {
+        let is_within = |mut id: widget::Id| loop {
+            if id == idx {
+                return true;
+            }
+            match graph.depth_parent(id) {
+                Some(parent) => id = parent,
+                None => return false,
+            }
+        };
+        let (front, mut back): (Vec<_>, Vec<_>) = self.indices.iter()
+            .cloned()
+            .partition(|&id| is_within(id));
+        back.extend(front);
+        self.indices = back;
+    }
+
 }
 
 
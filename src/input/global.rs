@@ -5,6 +5,7 @@
 use event;
 use input;
 use std;
+use Scalar;
 
 /// Global input event handler that also implements `input::Provider`. The `Ui` passes all events
 /// to it's `Global` instance, which aggregates and interprets the events to provide so-called
@@ -20,6 +21,11 @@ pub struct Global {
     /// Tracks the last click that occurred and the time at which it occurred in order to create
     /// double-click events.
     pub last_click: Option<(std::time::Instant, event::Click)>,
+    /// The time of the `Ui`'s frame clock at which the mouse last came to rest, used to display
+    /// tooltips.
+    ///
+    /// This is `None` if a mouse button has been pressed since the mouse last moved.
+    pub hover_start: Option<Scalar>,
 }
 
 /// Iterator over all global `event::Event`s that have occurred since the last time
@@ -45,6 +51,7 @@ impl Global {
             start: input::State::new(),
             current: input::State::new(),
             last_click: None,
+            hover_start: None,
         }
     }
 
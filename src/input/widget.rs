@@ -453,6 +453,9 @@ impl<'a> Iterator for Events<'a> {
                 event::Ui::Text(idx, ref text) if idx == Some(self.idx) =>
                     return Some(text.clone().into()),
 
+                event::Ui::Ime(idx, ref ime) if idx == Some(self.idx) =>
+                    return Some(ime.clone().into()),
+
                 event::Ui::Motion(idx, ref motion) if idx == Some(self.idx) =>
                     return Some(motion.clone().into()),
 
@@ -477,6 +480,21 @@ impl<'a> Iterator for Events<'a> {
                 event::Ui::Drag(idx, ref drag) if idx == Some(self.idx) =>
                     return Some(drag.clone().relative_to(self.rect.xy()).into()),
 
+                event::Ui::DragCancelled(idx, ref cancelled) if idx == Some(self.idx) =>
+                    return Some(cancelled.clone().relative_to(self.rect.xy()).into()),
+
+                event::Ui::DragEnter(idx, dnd) if idx == self.idx =>
+                    return Some(event::Widget::DragEnter(dnd.relative_to(self.rect.xy()))),
+
+                event::Ui::DragOver(idx, dnd) if idx == self.idx =>
+                    return Some(event::Widget::DragOver(dnd.relative_to(self.rect.xy()))),
+
+                event::Ui::DragLeave(idx, dnd) if idx == self.idx =>
+                    return Some(event::Widget::DragLeave(dnd.relative_to(self.rect.xy()))),
+
+                event::Ui::Drop(idx, dnd) if idx == self.idx =>
+                    return Some(event::Widget::Drop(dnd.relative_to(self.rect.xy()))),
+
                 event::Ui::Scroll(idx, ref scroll) if idx == Some(self.idx) =>
                     return Some(scroll.clone().into()),
 
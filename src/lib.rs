@@ -14,6 +14,8 @@ extern crate fnv;
 extern crate num;
 extern crate input as piston_input;
 extern crate rusttype;
+extern crate stb_truetype;
+extern crate unicode_segmentation;
 
 #[cfg(feature="glium")] #[macro_use] pub extern crate glium;
 #[cfg(feature="gfx_rs")] #[macro_use] pub extern crate gfx;
@@ -27,9 +29,12 @@ pub use theme::Theme;
 pub use ui::{Ui, UiCell, UiBuilder};
 pub use widget::{scroll, Widget};
 
+pub mod animation;
 pub mod backend;
 mod border;
+pub mod clipboard;
 pub mod color;
+pub mod drag_and_drop;
 pub mod event;
 pub mod graph;
 pub mod guide;
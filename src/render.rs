@@ -40,6 +40,23 @@ pub struct Primitives<'a> {
     triangles: Vec<Triangle<Point>>,
     /// The slice of rusttype `PositionedGlyph`s to re-use for the `Text` primitive.
     positioned_glyphs: Vec<text::PositionedGlyph>,
+    /// Primitives that are to be yielded before visiting the next widget.
+    ///
+    /// This is used for `Text` decorations, which are drawn as `Rectangle`s around the text.
+    queued: std::collections::VecDeque<Queued<'a>>,
+    /// A cache from which the glyphs of unchanged `Text` may be reused.
+    text_layout_cache: Option<&'a TextLayoutCache>,
+}
+
+/// The cache of text layouts shared between the `Ui` and its `Primitives`.
+pub type TextLayoutCache = std::cell::RefCell<text::layout_cache::LayoutCache>;
+
+/// A primitive queued for yielding by the `Primitives` iterator.
+enum Queued<'a> {
+    /// A `Rectangle` decorating some `Text`.
+    Rectangle { id: widget::Id, scizzor: Rect, rect: Rect, color: Color },
+    /// The `Text` itself.
+    Text { id: widget::Id, scizzor: Rect, container: &'a graph::Container },
 }
 
 /// An owned alternative to the `Primitives` type.
@@ -173,6 +190,8 @@ pub struct Text<'a> {
     justify: text::Justify,
     y_align: Align,
     line_spacing: Scalar,
+    tab_stops: text::TabStops,
+    layout_cache: Option<(&'a TextLayoutCache, text::layout_cache::Key)>,
 }
 
 
@@ -219,6 +238,7 @@ struct OwnedText {
     justify: text::Justify,
     y_align: Align,
     line_spacing: Scalar,
+    tab_stops: text::TabStops,
 }
 
 /// An iterator-like type for yielding `Primitive`s from an `OwnedPrimitives`.
@@ -257,6 +277,8 @@ impl<'a> Text<'a> {
             justify,
             y_align,
             line_spacing,
+            tab_stops,
+            layout_cache,
         } = self;
 
         // Convert conrod coordinates to pixel coordinates.
@@ -272,10 +294,28 @@ impl<'a> Text<'a> {
         // Clear the existing glyphs and fill the buffer with glyphs for this Text.
         positioned_glyphs.clear();
         let scale = text::pt_to_scale((font_size as f32 * dpi_factor) as FontSize);
-        for (line, line_rect) in lines.zip(line_rects) {
+        let line_points = line_rects.map(|line_rect| {
             let (x, y) = (trans_x(line_rect.left()) as f32, trans_y(line_rect.bottom()) as f32);
-            let point = text::rt::Point { x: x, y: y };
-            positioned_glyphs.extend(font.layout(line, scale, point).map(|g| g.standalone()));
+            text::rt::Point { x: x, y: y }
+        });
+
+        // Reuse the cached glyphs if possible, translating them to the position of each line.
+        if let Some((cache, ref key)) = layout_cache {
+            let mut cache = cache.borrow_mut();
+            let line_glyphs = cache.line_glyphs(key, text, font, dpi_factor);
+            for (glyphs, point) in line_glyphs.iter().zip(line_points) {
+                positioned_glyphs.extend(glyphs.iter().map(|g| {
+                    let position = g.position();
+                    let position = text::rt::point(position.x + point.x, position.y + point.y);
+                    g.unpositioned().clone().positioned(position)
+                }));
+            }
+            return positioned_glyphs;
+        }
+
+        for (line, point) in lines.zip(line_points) {
+            let layout = text::layout(line, font, scale, point, tab_stops).dpi_factor(dpi_factor);
+            positioned_glyphs.extend(layout.map(|g| g.standalone()));
         }
 
         positioned_glyphs
@@ -302,9 +342,20 @@ impl<'a> Primitives<'a> {
             window_rect: Rect::from_xy_dim([0.0, 0.0], window_dim),
             triangles: Vec::new(),
             positioned_glyphs: Vec::new(),
+            queued: std::collections::VecDeque::new(),
+            text_layout_cache: None,
         }
     }
 
+    /// Reuse the glyphs of unchanged `Text` from the given cache rather than laying them out for
+    /// every `Text` primitive.
+    ///
+    /// `Ui::draw` provides the `Ui`'s own text layout cache.
+    pub fn text_layout_cache(mut self, cache: &'a TextLayoutCache) -> Self {
+        self.text_layout_cache = Some(cache);
+        self
+    }
+
     /// Yield the next `Primitive` for rendering.
     pub fn next(&mut self) -> Option<Primitive> {
         let Primitives {
@@ -312,13 +363,39 @@ impl<'a> Primitives<'a> {
             ref mut depth_order,
             ref mut triangles,
             ref mut positioned_glyphs,
+            ref mut queued,
             graph,
             theme,
             fonts,
             window_rect,
+            text_layout_cache,
         } = *self;
 
-        while let Some(widget) = next_widget(depth_order, graph, crop_stack, window_rect) {
+        loop {
+
+            // Yield any primitives queued by the previous widget first.
+            match queued.pop_front() {
+                Some(Queued::Rectangle { id, scizzor, rect, color }) => {
+                    let kind = PrimitiveKind::Rectangle { color: color };
+                    return Some(new_primitive(id, kind, scizzor, rect));
+                },
+                Some(Queued::Text { id, scizzor, container }) => {
+                    if let Some(parts) = text_parts(container, theme, fonts) {
+                        let rect = container.rect;
+                        let kind = text_primitive_kind(parts, rect, positioned_glyphs, theme,
+                                                       window_rect, text_layout_cache);
+                        return Some(new_primitive(id, kind, scizzor, rect));
+                    }
+                    continue;
+                },
+                None => (),
+            }
+
+            let widget = match next_widget(depth_order, graph, crop_stack, window_rect) {
+                Some(widget) => widget,
+                None => return None,
+            };
+
             use widget::primitive::point_path::{State as PointPathState, Style as PointPathStyle};
             use widget::primitive::shape::polygon::{State as PolygonState};
             use widget::primitive::shape::Style as ShapeStyle;
@@ -568,43 +645,33 @@ impl<'a> Primitives<'a> {
                 }
 
             } else if container.type_id == state_type_id::<widget::Text>() {
-                if let Some(text) = container.unique_widget_state::<widget::Text>() {
-                    let graph::UniqueWidgetState { ref state, ref style } = *text;
-                    let font_id = match style.font_id(theme).or_else(|| fonts.ids().next()) {
-                        Some(id) => id,
-                        None => continue,
-                    };
-                    let font = match fonts.get(font_id) {
-                        Some(font) => font,
-                        None => continue,
-                    };
+                if let Some(parts) = text_parts(container, theme, fonts) {
+                    let (state, style, _, font) = parts;
+
+                    // Without decorations, the `Text` is the only primitive.
+                    if !style.has_decorations(theme) {
+                        let kind = text_primitive_kind(parts, rect, positioned_glyphs, theme,
+                                                       window_rect, text_layout_cache);
+                        return Some(new_primitive(id, kind, scizzor, rect));
+                    }
 
-                    // Retrieve styling.
-                    let color = style.color(theme);
+                    // Queue the backgrounds, then the text, then the lines over the text.
                     let font_size = style.font_size(theme);
-                    let line_spacing = style.line_spacing(theme);
-                    let justify = style.justify(theme);
-                    let y_align = Align::End;
-
-                    let text = Text {
-                        positioned_glyphs: positioned_glyphs,
-                        window_dim: window_rect.dim(),
-                        text: &state.string,
-                        line_infos: &state.line_infos,
-                        font: font,
-                        font_size: font_size,
-                        rect: rect,
-                        justify: justify,
-                        y_align: y_align,
-                        line_spacing: line_spacing,
-                    };
-
-                    let kind = PrimitiveKind::Text {
-                        color: color,
-                        text: text,
-                        font_id: font_id,
+                    let line_rects = text::line::rects(state.line_infos.iter().cloned(),
+                                                       font_size,
+                                                       rect,
+                                                       style.justify(theme),
+                                                       Align::End,
+                                                       style.line_spacing(theme));
+                    let decorations =
+                        widget::text::decorations(line_rects, font, font_size, style, theme);
+                    let queue_rect = |(rect, color): (Rect, Color)| {
+                        Queued::Rectangle { id: id, scizzor: scizzor, rect: rect, color: color }
                     };
-                    return Some(new_primitive(id, kind, scizzor, rect));
+                    queued.extend(decorations.backgrounds.into_iter().map(&queue_rect));
+                    queued.push_back(Queued::Text { id: id, scizzor: scizzor, container: container });
+                    queued.extend(decorations.lines.into_iter().map(&queue_rect));
+                    continue;
                 }
 
             } else if container.type_id == state_type_id::<widget::Image>() {
@@ -626,8 +693,6 @@ impl<'a> Primitives<'a> {
                 return Some(new_primitive(id, kind, scizzor, rect));
             }
         }
-
-        None
     }
 
     /// Collect the `Primitives` list into an owned collection.
@@ -697,6 +762,7 @@ impl<'a> Primitives<'a> {
                         justify,
                         y_align,
                         line_spacing,
+                        tab_stops,
                         ..
                     } = text;
 
@@ -724,6 +790,7 @@ impl<'a> Primitives<'a> {
                         justify: justify,
                         y_align: y_align,
                         line_spacing: line_spacing,
+                        tab_stops: tab_stops,
                     };
 
                     let kind = OwnedPrimitiveKind::Text {
@@ -831,6 +898,7 @@ impl<'a> WalkOwnedPrimitives<'a> {
                         justify,
                         y_align,
                         line_spacing,
+                        tab_stops,
                     } = *text;
 
                     let text_str = &texts_str[str_byte_range.clone()];
@@ -847,6 +915,8 @@ impl<'a> WalkOwnedPrimitives<'a> {
                         justify: justify,
                         y_align: y_align,
                         line_spacing: line_spacing,
+                        tab_stops: tab_stops,
+                        layout_cache: None,
                     };
 
                     let kind = PrimitiveKind::Text {
@@ -883,6 +953,76 @@ fn new_primitive(id: widget::Id, kind: PrimitiveKind, scizzor: Rect, rect: Rect)
     }
 }
 
+/// The unique state and style of the given `Text` widget's container, along with the `Id` of the
+/// font used by the widget and the font itself.
+///
+/// Returns `None` if the container is not a `Text` widget or if its font cannot be found.
+fn text_parts<'a>(container: &'a graph::Container,
+                  theme: &Theme,
+                  fonts: &'a text::font::Map)
+    -> Option<(&'a widget::text::State, &'a widget::text::Style, text::font::Id, &'a text::Font)>
+{
+    let text = match container.unique_widget_state::<widget::Text>() {
+        Some(text) => text,
+        None => return None,
+    };
+    let graph::UniqueWidgetState { ref state, ref style } = *text;
+    let font_id = match style.font_id(theme).or_else(|| fonts.ids().next()) {
+        Some(id) => id,
+        None => return None,
+    };
+    fonts.get(font_id).map(|font| (state, style, font_id, font))
+}
+
+/// Produce the `PrimitiveKind::Text` for a `Text` widget from the parts retrieved via `text_parts`.
+fn text_primitive_kind<'a>(parts: (&'a widget::text::State,
+                                   &'a widget::text::Style,
+                                   text::font::Id,
+                                   &'a text::Font),
+                           rect: Rect,
+                           positioned_glyphs: &'a mut Vec<text::PositionedGlyph>,
+                           theme: &Theme,
+                           window_rect: Rect,
+                           text_layout_cache: Option<&'a TextLayoutCache>) -> PrimitiveKind<'a>
+{
+    let (state, style, font_id, font) = parts;
+
+    // Retrieve styling.
+    let color = style.color(theme);
+    let font_size = style.font_size(theme);
+    let line_spacing = style.line_spacing(theme);
+    let justify = style.justify(theme);
+    let tab_stops = style.tab_stops(theme);
+    let y_align = Align::End;
+    let layout_cache = text_layout_cache.map(|cache| {
+        let maybe_wrap = style.maybe_wrap(theme);
+        let key = text::layout_cache::Key::new(&state.string, font_id, font_size, maybe_wrap,
+                                               rect.w(), justify, tab_stops);
+        (cache, key)
+    });
+
+    let text = Text {
+        positioned_glyphs: positioned_glyphs,
+        window_dim: window_rect.dim(),
+        text: &state.string,
+        line_infos: &state.line_infos,
+        font: font,
+        font_size: font_size,
+        rect: rect,
+        justify: justify,
+        y_align: y_align,
+        line_spacing: line_spacing,
+        tab_stops: tab_stops,
+        layout_cache: layout_cache,
+    };
+
+    PrimitiveKind::Text {
+        color: color,
+        text: text,
+        font_id: font_id,
+    }
+}
+
 /// Retrieves the next visible widget from the `depth_order`, updating the `crop_stack` as
 /// necessary.
 fn next_widget<'a>(depth_order: &mut std::slice::Iter<widget::Id>,
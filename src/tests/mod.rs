@@ -1,3 +1,5 @@
 mod global_input;
+mod text;
+mod text_edit;
 mod widget_input;
 mod ui;
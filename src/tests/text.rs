@@ -0,0 +1,92 @@
+use std;
+use FontSize;
+use position::{Align, Rect};
+use text;
+
+
+#[test]
+fn line_rects_below_should_skip_only_the_lines_above_the_given_y() {
+    let font = text::font::from_file("assets/fonts/NotoSans/NotoSans-Regular.ttf").unwrap();
+    let text = "line\n".repeat(20);
+    let font_size = 14;
+    let line_spacing = 2.0;
+    let infos: Vec<_> = text::line::infos(&text, &font, font_size).collect();
+    let bounding_rect = Rect::from_xy_dim([0.0, 0.0], [200.0, 400.0]);
+
+    for &y_align in &[Align::Start, Align::Middle, Align::End] {
+        let rects: Vec<_> = text::line::rects(infos.iter().cloned(), font_size, bounding_rect,
+                                              text::Justify::Left, y_align, line_spacing)
+            .collect();
+        for &y in &[500.0, 150.0, 0.0, -37.0, -500.0] {
+            let (first, rects_below) =
+                text::line::rects_below(&infos, font_size, bounding_rect, text::Justify::Left,
+                                        y_align, line_spacing, y);
+            assert!(rects[..first].iter().all(|rect| rect.bottom() > y));
+            assert!(rects[first..].iter().skip(1).all(|rect| rect.bottom() <= y));
+            let rects_below: Vec<_> = rects_below.collect();
+            assert_eq!(rects_below.len(), rects.len() - first);
+            for (a, b) in rects[first..].iter().zip(rects_below) {
+                assert!((a.bottom() - b.bottom()).abs() < 1e-6 && (a.top() - b.top()).abs() < 1e-6);
+                assert_eq!(a.x, b.x);
+            }
+        }
+    }
+}
+
+#[test]
+fn tab_stop_positions_should_be_computed_at_runtime_and_used_when_wrapping() {
+    let font = text::font::from_file("assets/fonts/NotoSans/NotoSans-Regular.ttf").unwrap();
+    let font_size = 14;
+    let columns: Vec<_> = (1..40).map(|i| i as f64 * 50.0).collect();
+    let tab_stops = text::TabStops::positions(&columns);
+    assert_eq!(tab_stops, text::TabStops::positions(&columns[..text::MAX_TAB_STOP_POSITIONS]));
+
+    let width = text::line::width_with_tab_stops("a\tb", &font, font_size, tab_stops);
+    let b_width = text::line::width("b", &font, font_size);
+    assert!((width - (50.0 + b_width)).abs() < 1.0);
+
+    // The tab advances to the stop at 100, wrapping the `b` that follows onto the next line.
+    let infos: Vec<_> = text::line::infos("a\t\tb", &font, font_size)
+        .tab_stops(tab_stops)
+        .wrap_by_character(100.0)
+        .collect();
+    assert_eq!(infos.len(), 2);
+    assert_eq!(infos[1].byte_range(), 3..4);
+
+    // Custom wrapping functions measure the text themselves.
+    fn break_at_end(text: &str, _: &text::Font, _: FontSize, _: f64)
+        -> (text::line::Break, f64)
+    {
+        (text::line::Break::End { byte: text.len(), char: text.chars().count() }, 0.0)
+    }
+    let infos: Vec<_> = text::line::infos_wrapped_by("a\tb", &font, font_size, 10.0, break_at_end)
+        .tab_stops(tab_stops)
+        .collect();
+    assert_eq!(infos.len(), 1);
+}
+
+#[test]
+fn font_map_should_find_inserted_fonts_by_family_and_style_name() {
+    let mut fonts = text::font::Map::new();
+    let regular = fonts.insert_from_file("assets/fonts/NotoSans/NotoSans-Regular.ttf").unwrap();
+    let bold_ids: Vec<_> = fonts
+        .insert_collection_from_file("assets/fonts/NotoSans/NotoSans-Bold.ttf")
+        .unwrap()
+        .collect();
+    let italic_bytes = std::fs::read("assets/fonts/NotoSans/NotoSans-Italic.ttf").unwrap();
+    let italic_collection = text::FontCollection::from_bytes(italic_bytes).unwrap();
+    let italic_ids: Vec<_> = fonts.insert_collection(italic_collection).collect();
+
+    assert_eq!(bold_ids.len(), 1);
+    assert_eq!(italic_ids.len(), 1);
+    assert_eq!(fonts.id_by_name("Noto Sans", "Regular"), Some(regular));
+    assert_eq!(fonts.id_by_name("noto sans", "BOLD"), Some(bold_ids[0]));
+    assert_eq!(fonts.id_by_name("Noto Sans", "Italic"), Some(italic_ids[0]));
+    assert_eq!(fonts.id_by_name("Noto Sans", "Bold Italic"), None);
+    assert_eq!(fonts.id_by_name("Noto Serif", "Regular"), None);
+
+    match fonts.insert_collection_from_file("assets/fonts/NotoSans/Missing.ttc") {
+        Err(text::font::Error::IO(_)) => (),
+        _ => panic!("expected an IO error for a missing collection file"),
+    }
+}
@@ -0,0 +1,216 @@
+use {Positionable, Sizeable, Widget, Ui, UiBuilder};
+use event::{self, Input};
+use input::{Button, Key, Motion, MouseButton};
+use widget::{self, text_edit};
+use widget::text_edit::Search;
+
+
+///// Test assist code.
+
+
+fn windowless_ui_with_font() -> Ui {
+    let mut ui = UiBuilder::new([800.0, 600.0]).build();
+    ui.fonts.insert_from_file("assets/fonts/NotoSans/NotoSans-Regular.ttf").unwrap();
+    ui
+}
+
+/// Instantiate the `TextEdit` in the middle of the window, updating `text` with its edits.
+fn set_text_edit<F>(ui: &mut Ui, id: widget::Id, text: &mut String, build: F)
+    -> Vec<text_edit::Event>
+    where F: FnOnce(widget::TextEdit) -> widget::TextEdit,
+{
+    let window = ui.window;
+    let events = {
+        let text_edit = widget::TextEdit::new(text).w_h(400.0, 200.0).middle_of(window);
+        build(text_edit).set(id, &mut ui.set_widgets())
+    };
+    for event in &events {
+        if let text_edit::Event::Update(ref new_text) = *event {
+            *text = new_text.clone();
+        }
+    }
+    events
+}
+
+/// Click on the given widget so that it captures the keyboard.
+fn focus(ui: &mut Ui, id: widget::Id) {
+    let xy = ui.xy_of(id).unwrap();
+    ui.handle_event(Input::Motion(Motion::MouseCursor { x: xy[0], y: xy[1] }));
+    ui.handle_event(Input::Press(Button::Mouse(MouseButton::Left)));
+    ui.handle_event(Input::Release(Button::Mouse(MouseButton::Left)));
+}
+
+fn undo_edits(ui: &Ui, id: widget::Id) -> Vec<String> {
+    let container = ui.widget_graph().widget(id).unwrap();
+    let unique = container.state_and_style::<text_edit::State, text_edit::Style>().unwrap();
+    unique.state.history().undo_edits().iter().map(|edit| edit.inserted.clone()).collect()
+}
+
+
+///// Actual tests.
+
+
+#[test]
+fn search_should_only_match_the_same_case_when_case_sensitive() {
+    let text = "Foo foo FOO";
+    assert_eq!(Search::new("foo").matches(text), vec![0..3, 4..7, 8..11]);
+    assert_eq!(Search::new("foo").case_sensitive(true).matches(text), vec![4..7]);
+    assert_eq!(Search::new("FOO").case_sensitive(true).matches(text), vec![8..11]);
+}
+
+#[test]
+fn search_should_only_match_whole_words_when_requested() {
+    let text = "foo food _foo foo";
+    assert_eq!(Search::new("foo").matches(text), vec![0..3, 4..7, 10..13, 14..17]);
+    assert_eq!(Search::new("foo").whole_word(true).matches(text), vec![0..3, 14..17]);
+}
+
+#[test]
+fn search_should_produce_non_overlapping_char_ranges() {
+    assert_eq!(Search::new("aa").matches("aaaaa"), vec![0..2, 2..4]);
+    assert_eq!(Search::new("é").matches("éé"), vec![0..1, 1..2]);
+    assert!(Search::new("").matches("abc").is_empty());
+}
+
+#[test]
+fn text_edit_should_wrap_around_when_finding_the_next_and_previous_matches() {
+    let ui = &mut windowless_ui_with_font();
+    let id = ui.widget_id_generator().next();
+    let text = &mut "foo bar foo".to_string();
+    let search = Search::new("foo");
+
+    set_text_edit(ui, id, text, |t| t.search(search).place_cursor(5));
+
+    let events = set_text_edit(ui, id, text, |t| t.search(search).find_next());
+    assert_eq!(events, vec![text_edit::Event::SelectionChanged { start: 8, end: 11 }]);
+    let events = set_text_edit(ui, id, text, |t| t.search(search).find_next());
+    assert_eq!(events, vec![text_edit::Event::SelectionChanged { start: 0, end: 3 }]);
+    let events = set_text_edit(ui, id, text, |t| t.search(search).find_prev());
+    assert_eq!(events, vec![text_edit::Event::SelectionChanged { start: 8, end: 11 }]);
+}
+
+#[test]
+fn text_edit_should_coalesce_typing_into_a_single_edit_per_word() {
+    let ui = &mut windowless_ui_with_font();
+    let id = ui.widget_id_generator().next();
+    let text = &mut String::new();
+
+    set_text_edit(ui, id, text, |t| t);
+    focus(ui, id);
+    set_text_edit(ui, id, text, |t| t);
+
+    for s in &["a", "b", " ", "c", "d"] {
+        ui.handle_event(Input::Text(s.to_string()));
+        set_text_edit(ui, id, text, |t| t);
+    }
+    assert_eq!(&text[..], "ab cd");
+    assert_eq!(undo_edits(ui, id), vec!["ab ".to_string(), "cd".to_string()]);
+
+    ui.handle_event(Input::Press(Button::Keyboard(Key::LCtrl)));
+    ui.handle_event(Input::Press(Button::Keyboard(Key::Z)));
+    ui.handle_event(Input::Release(Button::Keyboard(Key::Z)));
+    ui.handle_event(Input::Release(Button::Keyboard(Key::LCtrl)));
+    set_text_edit(ui, id, text, |t| t);
+    assert_eq!(&text[..], "ab ");
+
+    // Typing following an undo begins a new edit.
+    ui.handle_event(Input::Text("e".to_string()));
+    set_text_edit(ui, id, text, |t| t);
+    assert_eq!(&text[..], "ab e");
+    assert_eq!(undo_edits(ui, id), vec!["ab ".to_string(), "e".to_string()]);
+}
+
+#[test]
+fn text_edit_should_ignore_invalid_ime_cursors() {
+    let ui = &mut windowless_ui_with_font();
+    let id = ui.widget_id_generator().next();
+    let text = &mut "ab".to_string();
+
+    set_text_edit(ui, id, text, |t| t);
+    focus(ui, id);
+    set_text_edit(ui, id, text, |t| t);
+
+    // The cursor begins either within the two byte `é` or beyond the end of the text.
+    for cursor in vec![1..2, 3..9, 9..12] {
+        let preedit = event::Preedit { text: "é".to_string(), cursor: Some(cursor) };
+        ui.handle_event(Input::Ime(event::Ime::Preedit(preedit)));
+        set_text_edit(ui, id, text, |t| t);
+    }
+
+    ui.handle_event(Input::Ime(event::Ime::Commit("é".to_string())));
+    set_text_edit(ui, id, text, |t| t);
+    assert_eq!(&text[..], "abé");
+}
+
+#[test]
+fn text_edit_should_filter_and_limit_search_replacements_like_entered_text() {
+    let ui = &mut windowless_ui_with_font();
+    let id = ui.widget_id_generator().next();
+    let search = Search::new("1");
+
+    // Replacements rejected by the filter are not made.
+    let text = &mut "a1 b1".to_string();
+    let numeric = text_edit::Filter::Numeric;
+    set_text_edit(ui, id, text, |t| t.filter(numeric).search(search).replace_all("x"));
+    assert_eq!(&text[..], "a1 b1");
+    set_text_edit(ui, id, text, |t| t.filter(numeric).search(search).replace_all("x2"));
+    assert_eq!(&text[..], "a2 b2");
+
+    // Newlines are removed from the replacements of single line text.
+    let text = &mut "a1 b1".to_string();
+    set_text_edit(ui, id, text, |t| t.single_line(true).search(search).replace_all("\n3"));
+    assert_eq!(&text[..], "a3 b3");
+
+    // Replacing every match may not exceed the maximum length, while the replacement of the
+    // selected match is truncated to fit.
+    let text = &mut "a1 b1".to_string();
+    set_text_edit(ui, id, text, |t| t.max_length(6).search(search).replace_all("456"));
+    assert_eq!(&text[..], "a1 b1");
+    set_text_edit(ui, id, text, |t| t.max_length(6).search(search).find_next());
+    set_text_edit(ui, id, text, |t| t.max_length(6).search(search).replace("456"));
+    assert_eq!(&text[..], "a45 b1");
+}
+
+#[test]
+fn text_edit_should_insert_tabs_rather_than_move_the_focus_unless_single_line() {
+    let ui = &mut windowless_ui_with_font();
+    let (id, other) = {
+        let mut id_generator = ui.widget_id_generator();
+        (id_generator.next(), id_generator.next())
+    };
+    let focused = |ui: &Ui| ui.global_input().current.widget_capturing_keyboard;
+    let text = &mut "a".to_string();
+    let set_widgets = |ui: &mut Ui, text: &mut String, single_line: bool| {
+        let ui = &mut ui.set_widgets();
+        let events = widget::TextEdit::new(text)
+            .single_line(single_line)
+            .w_h(400.0, 200.0)
+            .middle_of(ui.window)
+            .set(id, ui);
+        for event in events {
+            if let text_edit::Event::Update(new_text) = event {
+                *text = new_text;
+            }
+        }
+        widget::Button::new().w_h(100.0, 50.0).set(other, ui);
+    };
+
+    set_widgets(ui, text, false);
+    focus(ui, id);
+    set_widgets(ui, text, false);
+    assert_eq!(focused(ui), Some(id));
+
+    // Some backends also produce text for the `Tab` press, which must not be inserted twice.
+    ui.handle_event(Input::Press(Button::Keyboard(Key::Tab)));
+    ui.handle_event(Input::Text("\t".to_string()));
+    ui.handle_event(Input::Release(Button::Keyboard(Key::Tab)));
+    set_widgets(ui, text, false);
+    assert_eq!(&text[..], "a\t");
+    assert_eq!(focused(ui), Some(id));
+
+    ui.handle_event(Input::Press(Button::Keyboard(Key::Tab)));
+    ui.handle_event(Input::Release(Button::Keyboard(Key::Tab)));
+    set_widgets(ui, text, true);
+    assert_eq!(&text[..], "a\t");
+    assert_eq!(focused(ui), Some(other));
+}
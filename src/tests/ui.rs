@@ -131,3 +131,271 @@ fn high_level_scroll_event_should_be_created_from_a_raw_mouse_scroll() {
         assert_eq!(expected_scroll, scroll);
     }
 }
+
+#[test]
+fn ui_should_copy_and_paste_through_the_installed_clipboard() {
+    let mut ui = windowless_ui();
+    assert_eq!(ui.clipboard_contents(), None);
+    ui.set_clipboard_contents("copied".to_string());
+    assert_eq!(ui.clipboard_contents(), Some("copied".to_string()));
+
+    ui.set_clipboard(::clipboard::Memory::new());
+    assert_eq!(ui.clipboard_contents(), None);
+}
+
+#[test]
+fn ui_should_animate_values_towards_their_targets() {
+    use animation::Easing;
+    let mut ui = windowless_ui();
+    let window = ui.window;
+
+    // A value begins at its first target.
+    assert_eq!(ui.set_widgets().animate(window, "x", 0.0, 1.0, Easing::Linear), 0.0);
+    assert!(!ui.is_animating());
+
+    // Changing the target begins animating towards it.
+    assert_eq!(ui.set_widgets().animate(window, "x", 10.0, 1.0, Easing::Linear), 0.0);
+    assert!(ui.is_animating());
+
+    ui.handle_event(Input::Update(0.5));
+    assert_eq!(ui.set_widgets().animate(window, "x", 10.0, 1.0, Easing::Linear), 5.0);
+
+    ui.advance(0.75);
+    assert_eq!(ui.set_widgets().animate(window, "x", 10.0, 1.0, Easing::Linear), 10.0);
+    assert!(!ui.is_animating());
+    assert_eq!(ui.time(), 1.25);
+}
+
+#[test]
+fn ui_should_move_the_keyboard_focus_through_the_tab_order() {
+    let ui = &mut windowless_ui();
+    let (first, second, rectangle) = {
+        let mut id_generator = ui.widget_id_generator();
+        (id_generator.next(), id_generator.next(), id_generator.next())
+    };
+
+    let set_widgets = |ui: &mut Ui| {
+        let ui = &mut ui.set_widgets();
+        widget::Button::new().w_h(100.0, 50.0).set(first, ui);
+        widget::Button::new().w_h(100.0, 50.0).tab_index(0).set(second, ui);
+        widget::Rectangle::fill([100.0, 50.0]).set(rectangle, ui);
+    };
+    let focused = |ui: &Ui| ui.global_input().current.widget_capturing_keyboard;
+
+    set_widgets(ui);
+    assert_eq!(ui.tab_order(), &[second, first]);
+    assert_eq!(focused(ui), None);
+
+    ui.handle_event(Input::Press(Button::Keyboard(Key::Tab)));
+    set_widgets(ui);
+    assert_eq!(focused(ui), Some(second));
+
+    ui.handle_event(Input::Press(Button::Keyboard(Key::Tab)));
+    set_widgets(ui);
+    assert_eq!(focused(ui), Some(first));
+
+    ui.handle_event(Input::Press(Button::Keyboard(Key::LShift)));
+    ui.handle_event(Input::Press(Button::Keyboard(Key::Tab)));
+    set_widgets(ui);
+    assert_eq!(focused(ui), Some(second));
+}
+
+#[test]
+fn ui_should_cancel_drags_and_release_the_mouse_when_escape_is_pressed() {
+    let ui = &mut windowless_ui();
+    let slider = ui.widget_id_generator().next();
+
+    let set_widgets = |ui: &mut Ui, value: f64| {
+        let window = ui.window;
+        widget::Slider::new(value, 0.0, 1.0)
+            .w_h(200.0, 30.0)
+            .middle_of(window)
+            .set(slider, &mut ui.set_widgets())
+    };
+
+    let mut value = 0.5;
+    set_widgets(ui, value);
+    let slider_xy = ui.xy_of(slider).unwrap();
+    move_mouse_to_abs_coordinates(slider_xy[0], slider_xy[1], ui);
+    press_mouse_button(MouseButton::Left, ui);
+    set_widgets(ui, value).map(|new_value| value = new_value);
+
+    move_mouse_to_abs_coordinates(50.0, 0.0, ui);
+    set_widgets(ui, value).map(|new_value| value = new_value);
+    assert!(value > 0.5);
+
+    ui.handle_event(Input::Press(Button::Keyboard(Key::Escape)));
+    assert_eq!(ui.global_input().current.widget_capturing_mouse, None);
+    assert!(ui.global_input().current.mouse.buttons.left().is_up());
+    assert_eq!(set_widgets(ui, value), Some(0.5));
+}
+
+#[test]
+fn ui_should_not_cancel_clicks_that_have_not_been_dragged_when_escape_is_pressed() {
+    let ui = &mut windowless_ui();
+    let button = ui.widget_id_generator().next();
+
+    let set_widgets = |ui: &mut Ui| {
+        let window = ui.window;
+        widget::Button::new()
+            .w_h(100.0, 30.0)
+            .middle_of(window)
+            .set(button, &mut ui.set_widgets())
+            .was_clicked()
+    };
+
+    set_widgets(ui);
+    move_mouse_to_abs_coordinates(0.0, 0.0, ui);
+    press_mouse_button(MouseButton::Left, ui);
+    set_widgets(ui);
+
+    ui.handle_event(Input::Press(Button::Keyboard(Key::Escape)));
+    assert!(ui.global_input().current.mouse.buttons.left().is_down());
+    release_mouse_button(MouseButton::Left, ui);
+    assert!(set_widgets(ui));
+}
+
+#[test]
+fn ui_should_drop_dragged_payloads_onto_widgets_that_accept_them() {
+    let ui = &mut windowless_ui();
+    let (source, target) = {
+        let mut id_generator = ui.widget_id_generator();
+        (id_generator.next(), id_generator.next())
+    };
+
+    // Produces the payload dropped onto the target, if any.
+    let set_widgets = |ui: &mut Ui| {
+        let ui = &mut ui.set_widgets();
+        widget::Rectangle::fill([100.0, 100.0]).x_y(-200.0, 0.0).set(source, ui);
+        widget::Rectangle::fill([100.0, 100.0]).x_y(200.0, 0.0).set(target, ui);
+
+        if ui.widget_input(source).drags().left().next().is_some() {
+            ui.start_drag(source, 42u32, None);
+        }
+
+        let mut dropped = None;
+        let events: Vec<_> = ui.widget_input(target).events().collect();
+        for event in events {
+            match event {
+                event::Widget::DragEnter(_) => ui.accept_drop(target),
+                event::Widget::Drop(dnd) => {
+                    assert_eq!(dnd.source, source);
+                    dropped = ui.drag_and_drop().and_then(|drag| drag.payload::<u32>()).cloned();
+                },
+                _ => (),
+            }
+        }
+        dropped
+    };
+
+    set_widgets(ui);
+    move_mouse_to_abs_coordinates(-200.0, 0.0, ui);
+    press_mouse_button(MouseButton::Left, ui);
+    assert_eq!(set_widgets(ui), None);
+
+    move_mouse_to_abs_coordinates(-180.0, 0.0, ui);
+    assert_eq!(set_widgets(ui), None);
+    assert_eq!(ui.drag_and_drop().map(|drag| drag.source()), Some(source));
+
+    let target_xy = ui.xy_of(target).unwrap();
+    move_mouse_to_abs_coordinates(target_xy[0], target_xy[1], ui);
+    assert_eq!(set_widgets(ui), None);
+    assert_eq!(ui.drag_and_drop().and_then(|drag| drag.target()), Some(target));
+    assert!(ui.drag_and_drop().map_or(false, |drag| drag.is_accepted()));
+
+    release_mouse_button(MouseButton::Left, ui);
+    assert_eq!(set_widgets(ui), Some(42));
+    assert!(ui.drag_and_drop().is_none());
+}
+
+#[test]
+fn ui_should_display_tooltips_once_the_mouse_rests_over_a_widget() {
+    let ui = &mut windowless_ui();
+    ui.theme.tooltip_delay = std::time::Duration::from_millis(500);
+    let (button, tooltip) = {
+        let mut id_generator = ui.widget_id_generator();
+        (id_generator.next(), id_generator.next())
+    };
+
+    let set_widgets = |ui: &mut Ui| {
+        let ui = &mut ui.set_widgets();
+        widget::Button::new().w_h(100.0, 50.0).tooltip_widget(tooltip).set(button, ui);
+    };
+
+    set_widgets(ui);
+    assert!(!ui.is_tooltip_pending());
+    let button_xy = ui.xy_of(button).unwrap();
+    move_mouse_to_abs_coordinates(button_xy[0], button_xy[1], ui);
+    assert!(ui.is_tooltip_pending());
+
+    // The tooltip is only displayed once the frame clock has passed the delay.
+    ui.handle_event(Input::Update(0.25));
+    set_widgets(ui);
+    assert_eq!(ui.tooltip(), None);
+    ui.handle_event(Input::Update(0.25));
+    set_widgets(ui);
+    assert_eq!(ui.tooltip(), Some((button, &widget::Tooltip::Widget(tooltip))));
+
+    // Clicking hides the tooltip until the mouse moves again.
+    left_click_mouse(ui);
+    assert_eq!(ui.tooltip(), None);
+    ui.handle_event(Input::Update(1.0));
+    set_widgets(ui);
+    assert_eq!(ui.tooltip(), None);
+
+    move_mouse_to_abs_coordinates(button_xy[0] + 10.0, button_xy[1], ui);
+    set_widgets(ui);
+    assert_eq!(ui.tooltip(), None);
+    ui.handle_event(Input::Update(0.5));
+    set_widgets(ui);
+    assert_eq!(ui.tooltip(), Some((button, &widget::Tooltip::Widget(tooltip))));
+}
+
+#[test]
+fn ui_should_display_the_tooltip_text_given_during_the_latest_stage() {
+    let ui = &mut windowless_ui();
+    let button = ui.widget_id_generator().next();
+
+    let set_widgets = |ui: &mut Ui, count: u32| {
+        let ui = &mut ui.set_widgets();
+        let text = match count {
+            0 => widget::TooltipText::from("Never clicked"),
+            n => ui.tooltip_text(format!("Clicked {} times", n)),
+        };
+        widget::Button::new().w_h(100.0, 50.0).tooltip(text).set(button, ui);
+    };
+    let text = |s: &str| widget::Tooltip::Text(s.to_string());
+
+    set_widgets(ui, 0);
+    let button_xy = ui.xy_of(button).unwrap();
+    move_mouse_to_abs_coordinates(button_xy[0], button_xy[1], ui);
+    ui.handle_event(Input::Update(1.0));
+    set_widgets(ui, 0);
+    assert_eq!(ui.tooltip(), Some((button, &text("Never clicked"))));
+
+    set_widgets(ui, 1);
+    assert_eq!(ui.tooltip(), Some((button, &text("Clicked 1 times"))));
+    set_widgets(ui, 2);
+    assert_eq!(ui.tooltip(), Some((button, &text("Clicked 2 times"))));
+}
+
+#[test]
+fn ui_should_produce_the_right_click_position_for_opening_popup_menus() {
+    let ui = &mut windowless_ui();
+    let button = ui.widget_id_generator().next();
+
+    let set_widgets = |ui: &mut Ui| {
+        let ui = &mut ui.set_widgets();
+        widget::Button::new().w_h(100.0, 50.0).x_y(20.0, 30.0).set(button, ui);
+    };
+
+    set_widgets(ui);
+    move_mouse_to_abs_coordinates(20.0, 30.0, ui);
+    left_click_mouse(ui);
+    assert_eq!(widget::popup_menu::right_click_xy(ui, button), None);
+
+    set_widgets(ui);
+    press_mouse_button(MouseButton::Right, ui);
+    release_mouse_button(MouseButton::Right, ui);
+    assert_eq!(widget::popup_menu::right_click_xy(ui, button), Some([20.0, 30.0]));
+}
@@ -28,8 +28,52 @@ pub struct Lines<'a, I> {
     ranges: I,
 }
 
+/// Lays out the glyphs of a single line of text, advancing each tab character to the next tab
+/// stop.
+///
+/// Aside from the handling of tabs, this behaves the same as the RustType `LayoutIter`. Each tab
+/// is laid out as a space glyph positioned at the start of the tab.
+pub struct Layout<'a, 'b> {
+    font: &'a ::rusttype::Font<'a>,
+    chars: std::str::Chars<'b>,
+    caret: f32,
+    start: rt::Point<f32>,
+    scale: Scale,
+    last_glyph: Option<GlyphId>,
+    tab_stops: TabStops,
+    dpi_factor: f32,
+}
+
+/// The positions at which tab characters end within a line of text.
+///
+/// Positions are measured from the start of each line.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum TabStops {
+    /// A stop every `n` space widths.
+    Spaces(usize),
+    /// A stop at each of the given distances from the start of the line, in ascending order.
+    ///
+    /// Tabs that begin after the last stop advance by the width of a single space.
+    ///
+    /// Use `TabStops::positions` to construct from a slice, e.g. of column widths computed at
+    /// runtime.
+    Positions(TabStopPositions),
+}
+
+/// The maximum number of positions that may be held by `TabStops::Positions`.
+pub const MAX_TAB_STOP_POSITIONS: usize = 16;
+
+/// Up to `MAX_TAB_STOP_POSITIONS` tab stop positions, stored inline so that `TabStops` is `Copy`.
+///
+/// Dereferences to the slice of positions.
+#[derive(Copy, Clone)]
+pub struct TabStopPositions {
+    positions: [Scalar; MAX_TAB_STOP_POSITIONS],
+    len: usize,
+}
+
 /// A type used for referring to typographic alignment of `Text`.
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub enum Justify {
     /// Align text to the start of the bounding `Rect`'s *x* axis.
     Left,
@@ -43,6 +87,17 @@ pub enum Justify {
     // TODO: Full,
 }
 
+/// The end of a line at which text is cut when it is too wide to fit within its bounds.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Ellipsis {
+    /// Cut the beginning of the line, e.g. `…of the line`.
+    Start,
+    /// Cut the middle of the line, e.g. `the e…line`.
+    Middle,
+    /// Cut the end of the line, e.g. `the end of…`.
+    End,
+}
+
 
 /// Determine the total height of a block of text with the given number of lines, font size and
 /// `line_spacing` (the space that separates each line of text).
@@ -67,6 +122,28 @@ pub fn lines<I>(text: &str, ranges: I) -> Lines<I>
 }
 
 
+/// Lay out the glyphs for a single line of `text` starting at the given `start` point.
+///
+/// Tab characters are advanced to the next of the given `tab_stops`.
+pub fn layout<'a, 'b>(text: &'b str,
+                      font: &'a ::rusttype::Font<'a>,
+                      scale: Scale,
+                      start: rt::Point<f32>,
+                      tab_stops: TabStops) -> Layout<'a, 'b>
+{
+    Layout {
+        font: font,
+        chars: text.chars(),
+        caret: 0.0,
+        start: start,
+        scale: scale,
+        last_glyph: None,
+        tab_stops: tab_stops,
+        dpi_factor: 1.0,
+    }
+}
+
+
 /// Converts the given font size in "points" to its font size in pixels.
 pub fn pt_to_px(font_size_in_points: FontSize) -> f32 {
     (font_size_in_points * 4) as f32 / 3.0
@@ -78,6 +155,123 @@ pub fn pt_to_scale(font_size_in_points: FontSize) -> Scale {
 }
 
 
+impl Default for TabStops {
+    fn default() -> Self {
+        TabStops::Spaces(4)
+    }
+}
+
+impl TabStops {
+
+    /// A stop at each of the given distances from the start of the line, in ascending order.
+    ///
+    /// Only the first `MAX_TAB_STOP_POSITIONS` positions are used.
+    pub fn positions(positions: &[Scalar]) -> Self {
+        TabStops::Positions(TabStopPositions::new(positions))
+    }
+
+    /// The width of a tab that begins at the given distance `x` from the start of the line.
+    ///
+    /// `space_width` is the advance width of a space in the font and scale being laid out, while
+    /// explicit `Positions` are multiplied by the given `scale_factor`.
+    pub fn advance(self, x: Scalar, space_width: Scalar, scale_factor: Scalar) -> Scalar {
+        match self {
+            TabStops::Spaces(n) => {
+                let interval = n as Scalar * space_width;
+                if interval <= 0.0 {
+                    return 0.0;
+                }
+                let next_stop = ((x / interval).floor() + 1.0) * interval;
+                next_stop - x
+            },
+            TabStops::Positions(positions) => {
+                positions.iter()
+                    .map(|&stop| stop * scale_factor)
+                    .find(|&stop| stop > x)
+                    .map(|stop| stop - x)
+                    .unwrap_or(space_width)
+            },
+        }
+    }
+
+}
+
+impl TabStopPositions {
+
+    /// Copy up to the first `MAX_TAB_STOP_POSITIONS` of the given positions.
+    pub fn new(positions: &[Scalar]) -> Self {
+        let len = std::cmp::min(positions.len(), MAX_TAB_STOP_POSITIONS);
+        let mut stops = TabStopPositions { positions: [0.0; MAX_TAB_STOP_POSITIONS], len: len };
+        stops.positions[..len].copy_from_slice(&positions[..len]);
+        stops
+    }
+
+}
+
+impl std::ops::Deref for TabStopPositions {
+    type Target = [Scalar];
+    fn deref(&self) -> &[Scalar] {
+        &self.positions[..self.len]
+    }
+}
+
+impl PartialEq for TabStopPositions {
+    fn eq(&self, other: &Self) -> bool {
+        self[..] == other[..]
+    }
+}
+
+impl std::fmt::Debug for TabStopPositions {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        self[..].fmt(f)
+    }
+}
+
+impl<'a, 'b> Layout<'a, 'b> {
+
+    /// Multiply explicit `TabStops::Positions` by the given factor.
+    ///
+    /// This should be used when laying out glyphs at some scale other than the one used for
+    /// positioning widgets, e.g. at the pixel density of the display while rendering.
+    pub fn dpi_factor(mut self, dpi_factor: f32) -> Self {
+        self.dpi_factor = dpi_factor;
+        self
+    }
+
+    /// The position of the caret along the *x* axis following the most recently yielded glyph.
+    pub fn caret_x(&self) -> f32 {
+        self.start.x + self.caret
+    }
+
+}
+
+impl<'a, 'b> Iterator for Layout<'a, 'b> {
+    type Item = ::rusttype::PositionedGlyph<'a>;
+    fn next(&mut self) -> Option<Self::Item> {
+        let Layout { font, ref mut chars, ref mut caret, start, scale, ref mut last_glyph,
+                     tab_stops, dpi_factor } = *self;
+        chars.next().map(|ch| {
+            if ch == '\t' {
+                let g = font.glyph(' ').scaled(scale);
+                let space_width = g.h_metrics().advance_width as Scalar;
+                let g = g.positioned(rt::point(start.x + *caret, start.y));
+                let advance = tab_stops.advance(*caret as Scalar, space_width, dpi_factor as Scalar);
+                *caret += advance as f32;
+                *last_glyph = None;
+                return g;
+            }
+            let g = font.glyph(ch).scaled(scale);
+            if let Some(last) = *last_glyph {
+                *caret += font.pair_kerning(scale, last, g.id());
+            }
+            let g = g.positioned(rt::point(start.x + *caret, start.y));
+            *caret += g.unpositioned().h_metrics().advance_width;
+            *last_glyph = Some(g.id());
+            g
+        })
+    }
+}
+
 impl<'a, I> Iterator for Lines<'a, I>
     where I: Iterator<Item=std::ops::Range<usize>>,
 {
@@ -171,22 +365,51 @@ pub mod font {
             Ok(self.insert(font))
         }
 
-        // /// Adds each font in the given `rusttype::FontCollection` to the `Map` and returns an
-        // /// iterator yielding a unique `Id` for each.
-        // pub fn insert_collection(&mut self, collection: super::FontCollection) -> NewIds {
-        //     let start_index = self.next_index;
-        //     let mut end_index = start_index;
-        //     for index in 0.. {
-        //         match collection.font_at(index) {
-        //             Some(font) => {
-        //                 self.insert(font);
-        //                 end_index += 1;
-        //             }
-        //             None => break,
-        //         }
-        //     }
-        //     NewIds { index_range: start_index..end_index }
-        // }
+        /// Adds each font in the given `rusttype::FontCollection` to the `Map` and returns an
+        /// iterator yielding a unique `Id` for each.
+        ///
+        /// Loading stops at the first font within the collection that fails to load.
+        pub fn insert_collection(&mut self, collection: super::FontCollection) -> NewIds {
+            let start_index = self.next_index;
+            for font in collection.into_fonts() {
+                match font {
+                    Ok(font) => { self.insert(font); },
+                    Err(_) => break,
+                }
+            }
+            NewIds { index_range: start_index..self.next_index }
+        }
+
+        /// Insert every `Font` within the collection (i.e. `.ttc` or `.otc`) at the given file
+        /// path into the map.
+        ///
+        /// Returns an `Error::NoFont` if the collection contained no fonts.
+        pub fn insert_collection_from_file<P>(&mut self, path: P) -> Result<NewIds, Error>
+            where P: AsRef<std::path::Path>,
+        {
+            let collection = collection_from_file(path)?;
+            let new_ids = self.insert_collection(collection);
+            if new_ids.index_range.start == new_ids.index_range.end {
+                return Err(Error::NoFont);
+            }
+            Ok(new_ids)
+        }
+
+        /// Find the `Id` of the font with the given family and style (aka "subfamily") names,
+        /// e.g. `("Noto Sans", "Bold")`.
+        ///
+        /// Names are compared case-insensitively. If more than one font matches, the one that
+        /// was inserted first is returned.
+        pub fn id_by_name(&self, family: &str, style: &str) -> Option<Id> {
+            let matches = |name: Option<String>, target: &str| {
+                name.map(|name| name.to_lowercase() == target.to_lowercase()).unwrap_or(false)
+            };
+            self.map.iter()
+                .filter(|&(_, font)| matches(family_name(font), family))
+                .filter(|&(_, font)| matches(style_name(font), style))
+                .map(|(&id, _)| id)
+                .min()
+        }
 
         /// Produces an iterator yielding the `Id` for each `Font` within the `Map`.
         pub fn ids(&self) -> Ids {
@@ -217,6 +440,73 @@ pub mod font {
     }
 
 
+    /// The family name of the given font, e.g. "Noto Sans".
+    ///
+    /// Returns `None` if the font's naming table contains no family name.
+    pub fn family_name(font: &super::Font) -> Option<String> {
+        name_string(font, NAME_ID_TYPOGRAPHIC_FAMILY)
+            .or_else(|| name_string(font, NAME_ID_FAMILY))
+    }
+
+    /// The style (aka "subfamily") name of the given font, e.g. "Bold" or "Italic".
+    ///
+    /// Returns `None` if the font's naming table contains no style name.
+    pub fn style_name(font: &super::Font) -> Option<String> {
+        name_string(font, NAME_ID_TYPOGRAPHIC_SUBFAMILY)
+            .or_else(|| name_string(font, NAME_ID_SUBFAMILY))
+    }
+
+    // Name IDs within the OpenType `name` table.
+    const NAME_ID_FAMILY: u16 = 1;
+    const NAME_ID_SUBFAMILY: u16 = 2;
+    const NAME_ID_TYPOGRAPHIC_FAMILY: u16 = 16;
+    const NAME_ID_TYPOGRAPHIC_SUBFAMILY: u16 = 17;
+
+    /// Read the first decodable string with the given name ID from the font's naming table.
+    ///
+    /// Unicode and Windows platform strings are encoded as UTF-16BE while Macintosh strings use
+    /// the Mac Roman encoding. Strings in any other encoding are skipped.
+    fn name_string(font: &super::Font, name_id: u16) -> Option<String> {
+        use stb_truetype::{MacEid, PlatformEncodingLanguageId as Encoding};
+
+        fn decode_utf16_be(bytes: &[u8]) -> String {
+            let units: Vec<u16> = bytes.chunks(2)
+                .filter(|pair| pair.len() == 2)
+                .map(|pair| (pair[0] as u16) << 8 | pair[1] as u16)
+                .collect();
+            String::from_utf16_lossy(&units)
+        }
+
+        fn decode_mac_roman(bytes: &[u8]) -> String {
+            bytes.iter()
+                .map(|&b| if b < 0x80 { b as char } else { MAC_ROMAN_HIGH[(b - 0x80) as usize] })
+                .collect()
+        }
+
+        font.font_name_strings()
+            .filter(|&(_, _, id)| id == name_id)
+            .filter_map(|(bytes, encoding, _)| match encoding {
+                Some(Encoding::Unicode(..)) |
+                Some(Encoding::Microsoft(..)) => Some(decode_utf16_be(bytes)),
+                Some(Encoding::Mac(Some(Ok(MacEid::Roman)), _)) => Some(decode_mac_roman(bytes)),
+                _ => None,
+            })
+            .find(|name| !name.is_empty())
+    }
+
+    // The characters for bytes `0x80...0xFF` in the Mac Roman encoding.
+    const MAC_ROMAN_HIGH: [char; 128] = [
+        'Ä', 'Å', 'Ç', 'É', 'Ñ', 'Ö', 'Ü', 'á', 'à', 'â', 'ä', 'ã', 'å', 'ç', 'é', 'è',
+        'ê', 'ë', 'í', 'ì', 'î', 'ï', 'ñ', 'ó', 'ò', 'ô', 'ö', 'õ', 'ú', 'ù', 'û', 'ü',
+        '†', '°', '¢', '£', '§', '•', '¶', 'ß', '®', '©', '™', '´', '¨', '≠', 'Æ', 'Ø',
+        '∞', '±', '≤', '≥', '¥', 'µ', '∂', '∑', '∏', 'π', '∫', 'ª', 'º', 'Ω', 'æ', 'ø',
+        '¿', '¡', '¬', '√', 'ƒ', '≈', '∆', '«', '»', '…', '\u{A0}', 'À', 'Ã', 'Õ', 'Œ', 'œ',
+        '–', '—', '“', '”', '‘', '’', '÷', '◊', 'ÿ', 'Ÿ', '⁄', '€', '‹', '›', 'ﬁ', 'ﬂ',
+        '‡', '·', '‚', '„', '‰', 'Â', 'Ê', 'Á', 'Ë', 'È', 'Í', 'Î', 'Ï', 'Ì', 'Ó', 'Ô',
+        '\u{F8FF}', 'Ò', 'Ú', 'Û', 'Ù', 'ı', 'ˆ', '˜', '¯', '˘', '˙', '˚', '¸', '˝', '˛', 'ˇ',
+    ];
+
+
     impl Iterator for NewIds {
         type Item = Id;
         fn next(&mut self) -> Option<Self::Item> {
@@ -277,8 +567,8 @@ pub mod glyph {
         y: Range,
         /// The position of the next `Rect`'s left edge along the *x* axis.
         next_left: Scalar,
-        /// `PositionedGlyphs` yielded by the RustType `LayoutIter`.
-        layout: super::LayoutIter<'a, 'b>,
+        /// `PositionedGlyphs` yielded by the tab-aware `Layout`.
+        layout: super::Layout<'a, 'b>,
     }
 
     /// An iterator that, for every `(line, line_rect)` pair yielded by the given iterator,
@@ -287,6 +577,7 @@ pub mod glyph {
         lines_with_rects: I,
         font: &'a super::Font,
         font_size: FontSize,
+        tab_stops: super::TabStops,
     }
 
     /// Yields an iteraor yielding `Rect`s for each selected character in each line of text within
@@ -297,7 +588,9 @@ pub mod glyph {
     ///
     /// All lines that have no selected `Rect`s will be skipped.
     pub struct SelectedRectsPerLine<'a, I> {
-        enumerated_rects_per_line: std::iter::Enumerate<RectsPerLine<'a, I>>,
+        rects_per_line: RectsPerLine<'a, I>,
+        /// The index of the next line yielded by `rects_per_line`.
+        next_line: usize,
         start_cursor_idx: super::cursor::Index,
         end_cursor_idx: super::cursor::Index,
     }
@@ -343,6 +636,7 @@ pub mod glyph {
             lines_with_rects: lines_with_rects,
             font: font,
             font_size: font_size,
+            tab_stops: super::TabStops::default(),
         }
     }
 
@@ -361,26 +655,42 @@ pub mod glyph {
         where I: Iterator<Item=(&'a str, Rect)>,
     {
         SelectedRectsPerLine {
-            enumerated_rects_per_line:
-                rects_per_line(lines_with_rects, font, font_size).enumerate(),
+            rects_per_line: rects_per_line(lines_with_rects, font, font_size),
+            next_line: 0,
             start_cursor_idx: start,
             end_cursor_idx: end,
         }
     }
 
+    impl<'a, I> RectsPerLine<'a, I> {
+        /// Lay out tab characters using the given `TabStops`.
+        pub fn tab_stops(mut self, tab_stops: super::TabStops) -> Self {
+            self.tab_stops = tab_stops;
+            self
+        }
+    }
+
+    impl<'a, I> SelectedRectsPerLine<'a, I> {
+        /// Lay out tab characters using the given `TabStops`.
+        pub fn tab_stops(mut self, tab_stops: super::TabStops) -> Self {
+            self.rects_per_line = self.rects_per_line.tab_stops(tab_stops);
+            self
+        }
+    }
+
     impl<'a, I> Iterator for RectsPerLine<'a, I>
         where I: Iterator<Item=(&'a str, Rect)>,
     {
         type Item = Rects<'a, 'a>;
         fn next(&mut self) -> Option<Self::Item> {
-            let RectsPerLine { ref mut lines_with_rects, font, font_size } = *self;
+            let RectsPerLine { ref mut lines_with_rects, font, font_size, tab_stops } = *self;
             let scale = super::pt_to_scale(font_size);
             lines_with_rects.next().map(|(line, line_rect)| {
                 let (x, y) = (line_rect.left() as f32, line_rect.top() as f32);
                 let point = super::rt::Point { x: x, y: y };
                 Rects {
                     next_left: line_rect.x.start,
-                    layout: font.layout(line, scale, point),
+                    layout: super::layout(line, font, scale, point, tab_stops),
                     y: line_rect.y
                 }
             })
@@ -393,12 +703,15 @@ pub mod glyph {
         type Item = SelectedRects<'a, 'a>;
         fn next(&mut self) -> Option<Self::Item> {
             let SelectedRectsPerLine {
-                ref mut enumerated_rects_per_line,
+                ref mut rects_per_line,
+                ref mut next_line,
                 start_cursor_idx,
                 end_cursor_idx,
             } = *self;
 
-            enumerated_rects_per_line.next().map(|(i, rects)| {
+            rects_per_line.next().map(|rects| {
+                let i = *next_line;
+                *next_line += 1;
                 let end_char_idx =
                     // If this is the last line, the end is the char after the final selected char.
                     if i == end_cursor_idx.line {
@@ -436,7 +749,7 @@ pub mod glyph {
                 let left = *next_left;
                 let right = g.pixel_bounding_box()
                     .map(|bb| bb.max.x as Scalar)
-                    .unwrap_or_else(|| left + g.unpositioned().h_metrics().advance_width as Scalar);
+                    .unwrap_or_else(|| layout.caret_x() as Scalar);
                 *next_left = right;
                 let x = Range::new(left, right);
                 Rect { x: x, y: y }
@@ -464,6 +777,7 @@ pub mod cursor {
     use FontSize;
     use position::{Range, Rect, Scalar, Point, Align};
     use std;
+    use unicode_segmentation::UnicodeSegmentation;
 
     /// Every possible cursor position within each line of text yielded by the given iterator.
     ///
@@ -475,6 +789,7 @@ pub mod cursor {
         font: &'a super::Font,
         text: &'a str,
         font_size: FontSize,
+        tab_stops: super::TabStops,
     }
 
     /// Similarly to `XysPerLine`, yields every possible cursor position within each line of text
@@ -497,9 +812,15 @@ pub mod cursor {
     /// Each possible cursor position along the *x* axis within a line of text.
     ///
     /// `Xs` iterators are produced by the `XysPerLine` iterator.
+    ///
+    /// Positions that lie within an extended grapheme cluster (e.g. between a base character and
+    /// its combining marks) yield the same *x* as the start of that cluster, so that each cluster
+    /// maps to a single caret position.
     pub struct Xs<'a, 'b> {
         next_x: Option<Scalar>,
-        layout: super::LayoutIter<'a, 'b>,
+        layout: super::Layout<'a, 'b>,
+        boundaries: std::vec::IntoIter<bool>,
+        cluster_x: Scalar,
     }
 
     /// An index representing the position of a cursor within some text.
@@ -547,6 +868,8 @@ pub mod cursor {
                             break
                         }
                     }
+                    let line_text = &text[line_info.byte_range()];
+                    let new_char = snap_to_grapheme_boundary(line_text, new_char);
                     Some(Index { line: line, char: new_char })
                 })
             } else {
@@ -587,6 +910,8 @@ pub mod cursor {
                                 break
                             }
                         }
+                        let line_text = &text[line_info.byte_range()];
+                        let new_char = snap_to_grapheme_boundary(line_text, new_char);
                         Some(Index { line: line, char: new_char })
                     } else {
                         line_infos.next().map(|_| Index { line: line + 1, char: 0 })
@@ -650,6 +975,83 @@ pub mod cursor {
                 })
         }
 
+        /// The cursor index of the grapheme cluster boundary that comes before `self`.
+        ///
+        /// This behaves like `previous`, but steps over a whole extended grapheme cluster (e.g. a
+        /// base character along with its combining marks, or an emoji sequence) at a time rather
+        /// than over a single `char`.
+        pub fn previous_grapheme<I>(self, text: &str, mut line_infos: I) -> Option<Self>
+            where I: Iterator<Item=super::line::Info>,
+        {
+            let Index { line, char } = self;
+            if char > 0 {
+                line_infos.nth(line)
+                    .and_then(|info| if char <= info.char_range().count() {
+                        let line_text = &text[info.byte_range()];
+                        let new_char = previous_grapheme_boundary(line_text, char);
+                        Some(Index { line: line, char: new_char })
+                    } else {
+                        None
+                    })
+            } else {
+                self.previous(line_infos)
+            }
+        }
+
+        /// The cursor index of the grapheme cluster boundary that follows `self`.
+        ///
+        /// This behaves like `next`, but steps over a whole extended grapheme cluster (e.g. a
+        /// base character along with its combining marks, or an emoji sequence) at a time rather
+        /// than over a single `char`.
+        pub fn next_grapheme<I>(self, text: &str, mut line_infos: I) -> Option<Self>
+            where I: Iterator<Item=super::line::Info>,
+        {
+            let Index { line, char } = self;
+            line_infos.nth(line)
+                .and_then(|info| {
+                    if char >= info.char_range().count() {
+                        line_infos.next().map(|_| Index { line: line + 1, char: 0 })
+                    } else {
+                        let line_text = &text[info.byte_range()];
+                        let new_char = next_grapheme_boundary(line_text, char);
+                        Some(Index { line: line, char: new_char })
+                    }
+                })
+        }
+
+        /// The cursor indices at the start and end of the word (block of non-whitespace) that
+        /// contains `self`.
+        ///
+        /// If `self` points to whitespace, the range of that block of whitespace is returned
+        /// instead. If `self` is at the end of a line, the block before `self` is used.
+        ///
+        /// Returns `None` if `self.line` is out of range of the given `line_infos`.
+        pub fn word_range<I>(self, text: &str, mut line_infos: I) -> Option<(Self, Self)>
+            where I: Iterator<Item=super::line::Info>,
+        {
+            let Index { line, char } = self;
+            line_infos.nth(line).map(|line_info| {
+                let chars: Vec<char> = text[line_info.byte_range()].chars().collect();
+                if chars.is_empty() {
+                    return (self, self);
+                }
+                let i = std::cmp::min(char, chars.len() - 1);
+                let is_whitespace = chars[i].is_whitespace();
+                let mut start = i;
+                while start > 0 && chars[start - 1].is_whitespace() == is_whitespace {
+                    start -= 1;
+                }
+                let mut end = i + 1;
+                while end < chars.len() && chars[end].is_whitespace() == is_whitespace {
+                    end += 1;
+                }
+                let line_text = &text[line_info.byte_range()];
+                let start = snap_to_grapheme_boundary(line_text, start);
+                let end = snap_to_grapheme_boundary(line_text, end);
+                (Index { line: line, char: start }, Index { line: line, char: end })
+            })
+        }
+
         /// Clamps `self` to the given lines.
         ///
         /// If `self` would lie after the end of the last line, return the index at the end of the
@@ -680,6 +1082,60 @@ pub mod cursor {
     }
 
 
+    /// Whether or not each cursor position within the given line lies on an extended grapheme
+    /// cluster boundary.
+    ///
+    /// The returned `Vec` contains `line.chars().count() + 1` elements, one for each cursor
+    /// position.
+    pub fn grapheme_boundaries(line: &str) -> Vec<bool> {
+        let mut boundaries = vec![false; line.chars().count() + 1];
+        boundaries[0] = true;
+        let mut char = 0;
+        for grapheme in line.graphemes(true) {
+            char += grapheme.chars().count();
+            boundaries[char] = true;
+        }
+        boundaries
+    }
+
+    /// The char index of the last grapheme cluster boundary within `line` that lies before the
+    /// given `char` index.
+    ///
+    /// Returns `0` if `char` is `0`.
+    pub fn previous_grapheme_boundary(line: &str, char: usize) -> usize {
+        let mut boundary = 0;
+        for grapheme in line.graphemes(true) {
+            let next = boundary + grapheme.chars().count();
+            if next >= char {
+                break;
+            }
+            boundary = next;
+        }
+        boundary
+    }
+
+    /// The char index of the first grapheme cluster boundary within `line` that lies after the
+    /// given `char` index.
+    ///
+    /// Returns the number of chars in `line` if there are no more boundaries.
+    pub fn next_grapheme_boundary(line: &str, char: usize) -> usize {
+        let mut boundary = 0;
+        for grapheme in line.graphemes(true) {
+            boundary += grapheme.chars().count();
+            if boundary > char {
+                break;
+            }
+        }
+        boundary
+    }
+
+    /// Snaps the given `char` index back to the start of the grapheme cluster that contains it.
+    ///
+    /// Indices that already lie on a boundary are returned unchanged.
+    fn snap_to_grapheme_boundary(line: &str, char: usize) -> usize {
+        previous_grapheme_boundary(line, char + 1)
+    }
+
     /// Every possible cursor position within each line of text yielded by the given iterator.
     ///
     /// Yields `(xs, y_range)`, where `y_range` is the `Range` occupied by the line across the *y*
@@ -694,6 +1150,7 @@ pub mod cursor {
             font: font,
             text: text,
             font_size: font_size,
+            tab_stops: super::TabStops::default(),
         }
     }
 
@@ -817,14 +1274,32 @@ pub mod cursor {
             if diff < closest_diff {
                 closest = (i,x);
                 closest_diff = diff;
-            } else {
+            } else if diff > closest_diff {
                 break;
             }
+            // Positions within a grapheme cluster share the `x` of the cluster's start, so ties
+            // are skipped rather than ending the search.
         }
         closest
     }
 
 
+    impl<'a, I> XysPerLine<'a, I> {
+        /// Lay out tab characters using the given `TabStops`.
+        pub fn tab_stops(mut self, tab_stops: super::TabStops) -> Self {
+            self.tab_stops = tab_stops;
+            self
+        }
+    }
+
+    impl<'a> XysPerLineFromText<'a> {
+        /// Lay out tab characters using the given `TabStops`.
+        pub fn tab_stops(mut self, tab_stops: super::TabStops) -> Self {
+            self.xys_per_line = self.xys_per_line.tab_stops(tab_stops);
+            self
+        }
+    }
+
     impl<'a, I> Iterator for XysPerLine<'a, I>
         where I: Iterator<Item=(super::line::Info, Rect)>,
     {
@@ -832,17 +1307,19 @@ pub mod cursor {
         // each possible cursor position along the *x* axis.
         type Item = (Xs<'a, 'a>, Range);
         fn next(&mut self) -> Option<Self::Item> {
-            let XysPerLine { ref mut lines_with_rects, font, text, font_size } = *self;
+            let XysPerLine { ref mut lines_with_rects, font, text, font_size, tab_stops } = *self;
             let scale = super::pt_to_scale(font_size);
             lines_with_rects.next().map(|(line_info, line_rect)| {
                 let line = &text[line_info.byte_range()];
                 let (x, y) = (line_rect.left() as f32, line_rect.top() as f32);
                 let point = super::rt::Point { x: x, y: y };
                 let y = line_rect.y;
-                let layout = font.layout(line, scale, point);
+                let layout = super::layout(line, font, scale, point, tab_stops);
                 let xs = Xs {
                     next_x: Some(line_rect.x.start),
                     layout: layout,
+                    boundaries: grapheme_boundaries(line).into_iter(),
+                    cluster_x: line_rect.x.start,
                 };
                 (xs, y)
             })
@@ -861,15 +1338,17 @@ pub mod cursor {
         type Item = Scalar;
         fn next(&mut self) -> Option<Self::Item> {
             self.next_x.map(|x| {
-                self.next_x = self.layout.next()
+                let layout = &mut self.layout;
+                self.next_x = layout.next()
                     .map(|g| {
                         g.pixel_bounding_box()
                             .map(|r| r.max.x as Scalar)
-                            .unwrap_or_else(|| {
-                                x + g.unpositioned().h_metrics().advance_width as Scalar
-                            })
+                            .unwrap_or_else(|| layout.caret_x() as Scalar)
                     });
-                x
+                if self.boundaries.next().unwrap_or(true) {
+                    self.cluster_x = x;
+                }
+                self.cluster_x
             })
         }
     }
@@ -947,6 +1426,7 @@ pub mod line {
         font: &'a super::Font,
         font_size: FontSize,
         max_width: Scalar,
+        tab_stops: super::TabStops,
         next_break_fn: F,
         /// The index that indicates the start of the next line to be yielded.
         start_byte: usize,
@@ -978,6 +1458,36 @@ pub mod line {
     /// wrapping function.
     pub type NextBreakFnPtr = fn(&str, &super::Font, FontSize, Scalar) -> (Break, Scalar);
 
+    /// The built-in methods by which `Infos` breaks lines of text, selected via the `infos`
+    /// function and the `Infos::wrap_by_character` and `Infos::wrap_by_whitespace` methods.
+    ///
+    /// Unlike custom wrapping functions, these lay out tabs using the `Infos`' `TabStops`.
+    #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+    pub enum NextBreakBy {
+        /// Only break lines at newline characters.
+        Newline,
+        /// Also wrap lines at the character that first exceeds the maximum width.
+        Character,
+        /// Also wrap lines at the whitespace preceding the word that first exceeds the maximum
+        /// width.
+        Whitespace,
+    }
+
+    /// Types that may be used by `Infos` to find the next break within the given text, along
+    /// with the width of the line up to the break.
+    ///
+    /// This is implemented for `NextBreakBy` and for all wrapping functions compatible with
+    /// `NextBreakFnPtr`.
+    pub trait NextBreak {
+        /// Find the next break within `text`, laying out any tabs using the given `tab_stops`.
+        fn next_break(&mut self,
+                      text: &str,
+                      font: &super::Font,
+                      font_size: FontSize,
+                      max_width: Scalar,
+                      tab_stops: super::TabStops) -> (Break, Scalar);
+    }
+
 
     impl Break {
 
@@ -1012,6 +1522,7 @@ pub mod line {
                 font: self.font,
                 font_size: self.font_size,
                 max_width: self.max_width,
+                tab_stops: self.tab_stops,
                 next_break_fn: self.next_break_fn.clone(),
                 start_byte: self.start_byte,
                 start_char: self.start_char,
@@ -1044,12 +1555,24 @@ pub mod line {
 
     }
 
-    impl<'a> Infos<'a, NextBreakFnPtr> {
+    impl<'a, F> Infos<'a, F> {
+
+        /// Lay out tab characters using the given `TabStops`.
+        ///
+        /// By default, `TabStops::default()` is used.
+        pub fn tab_stops(mut self, tab_stops: super::TabStops) -> Self {
+            self.tab_stops = tab_stops;
+            self
+        }
+
+    }
+
+    impl<'a> Infos<'a, NextBreakBy> {
 
         /// Converts `Self` into an `Infos` whose lines are wrapped at the character that first
         /// causes the line width to exceed the given `max_width`.
         pub fn wrap_by_character(mut self, max_width: Scalar) -> Self {
-            self.next_break_fn = next_break_by_character;
+            self.next_break_fn = NextBreakBy::Character;
             self.max_width = max_width;
             self
         }
@@ -1057,7 +1580,7 @@ pub mod line {
         /// Converts `Self` into an `Infos` whose lines are wrapped at the whitespace prior to the
         /// character that causes the line width to exceed the given `max_width`.
         pub fn wrap_by_whitespace(mut self, max_width: Scalar) -> Self {
-            self.next_break_fn = next_break_by_whitespace;
+            self.next_break_fn = NextBreakBy::Whitespace;
             self.max_width = max_width;
             self
         }
@@ -1070,14 +1593,23 @@ pub mod line {
     ///
     /// This also updates the `last_glyph` with the glyph produced for the given `char`.
     ///
+    /// A tab character advances from the given line position `x` to the next of the `tab_stops`.
+    ///
     /// This is primarily for use within the `next_break` functions below.
     ///
     /// The following code is adapted from the rusttype::LayoutIter::next src.
     fn advance_width(ch: char,
+                     x: Scalar,
                      font: &super::Font,
                      scale: super::Scale,
+                     tab_stops: super::TabStops,
                      last_glyph: &mut Option<super::GlyphId>) -> Scalar
     {
+        if ch == '\t' {
+            let space_width = font.glyph(' ').scaled(scale).h_metrics().advance_width as Scalar;
+            *last_glyph = None;
+            return tab_stops.advance(x, space_width, 1.0);
+        }
         let g = font.glyph(ch).scaled(scale);
         let kern = last_glyph
             .map(|last| font.pair_kerning(scale, last, g.id()))
@@ -1092,7 +1624,8 @@ pub mod line {
     /// along with the width of the line.
     fn next_break(text: &str,
                   font: &super::Font,
-                  font_size: FontSize) -> (Break, Scalar)
+                  font_size: FontSize,
+                  tab_stops: super::TabStops) -> (Break, Scalar)
     {
         let scale = super::pt_to_scale(font_size);
         let mut width = 0.0;
@@ -1112,7 +1645,7 @@ pub mod line {
             }
 
             // Update the width.
-            width += advance_width(ch, font, scale, &mut last_glyph);
+            width += advance_width(ch, width, font, scale, tab_stops, &mut last_glyph);
             char_i += 1;
         }
         let break_ = Break::End { byte: text.len(), char: char_i };
@@ -1127,7 +1660,8 @@ pub mod line {
     fn next_break_by_character(text: &str,
                                font: &super::Font,
                                font_size: FontSize,
-                               max_width: Scalar) -> (Break, Scalar)
+                               max_width: Scalar,
+                               tab_stops: super::TabStops) -> (Break, Scalar)
     {
         let scale = super::pt_to_scale(font_size);
         let mut width = 0.0;
@@ -1148,7 +1682,7 @@ pub mod line {
             }
 
             // Add the character's width to the width so far.
-            let new_width = width + advance_width(ch, font, scale, &mut last_glyph);
+            let new_width = width + advance_width(ch, width, font, scale, tab_stops, &mut last_glyph);
 
             // Check for a line wrap.
             if new_width > max_width {
@@ -1175,7 +1709,8 @@ pub mod line {
     fn next_break_by_whitespace(text: &str,
                                 font: &super::Font,
                                 font_size: FontSize,
-                                max_width: Scalar) -> (Break, Scalar)
+                                max_width: Scalar,
+                                tab_stops: super::TabStops) -> (Break, Scalar)
     {
         struct Last { byte: usize, char: usize, width_before: Scalar }
         let scale = super::pt_to_scale(font_size);
@@ -1198,7 +1733,7 @@ pub mod line {
             }
 
             // Add the character's width to the width so far.
-            let new_width = width + advance_width(ch, font, scale, &mut last_glyph);
+            let new_width = width + advance_width(ch, width, font, scale, tab_stops, &mut last_glyph);
 
             // Check for a line wrap.
             if width > max_width {
@@ -1229,15 +1764,28 @@ pub mod line {
 
 
     /// Produce the width of the given line of text including spaces (i.e. ' ').
+    ///
+    /// Tabs are laid out using `TabStops::default()`.
     pub fn width(text: &str, font: &super::Font, font_size: FontSize) -> Scalar {
+        width_with_tab_stops(text, font, font_size, super::TabStops::default())
+    }
+
+    /// Produce the width of the given line of text including spaces (i.e. ' '), laying out tabs
+    /// using the given `tab_stops`.
+    pub fn width_with_tab_stops(text: &str,
+                                font: &super::Font,
+                                font_size: FontSize,
+                                tab_stops: super::TabStops) -> Scalar
+    {
         let scale = super::Scale::uniform(super::pt_to_px(font_size));
         let point = super::rt::Point { x: 0.0, y: 0.0 };
 
         let mut total_w = 0.0;
-        for g in font.layout(text, scale, point) {
+        let mut layout = super::layout(text, font, scale, point, tab_stops);
+        while let Some(g) = layout.next() {
             match g.pixel_bounding_box() {
                 Some(bb) => total_w = bb.max.x as f32,
-                None => total_w += g.unpositioned().h_metrics().advance_width,
+                None => total_w = layout.caret_x(),
             }
         }
 
@@ -1245,6 +1793,63 @@ pub mod line {
     }
 
 
+    /// Shorten the given line of text so that it fits within the given `max_width`, replacing the
+    /// removed characters with a "…".
+    ///
+    /// The line is cut at a `char` boundary at the position described by `ellipsis`. Whitespace
+    /// directly adjacent to the "…" is removed.
+    ///
+    /// Returns the line unchanged if it already fits within `max_width`. If not even the "…" fits,
+    /// the "…" is returned alone.
+    pub fn ellipsize<'a>(line: &'a str,
+                         font: &super::Font,
+                         font_size: FontSize,
+                         max_width: Scalar,
+                         ellipsis: super::Ellipsis) -> std::borrow::Cow<'a, str>
+    {
+        const ELLIPSIS: &'static str = "\u{2026}";
+
+        if width(line, font, font_size) <= max_width {
+            return std::borrow::Cow::Borrowed(line);
+        }
+
+        // Byte index of each char boundary, including the end of the line.
+        let boundaries: Vec<usize> = line.char_indices()
+            .map(|(i, _)| i)
+            .chain(Some(line.len()))
+            .collect();
+        let num_chars = boundaries.len() - 1;
+
+        // Produce the shortened line that retains `n` of the line's characters.
+        let shortened = |n: usize| {
+            let (head, tail) = match ellipsis {
+                super::Ellipsis::Start => (0, n),
+                super::Ellipsis::Middle => ((n + 1) / 2, n / 2),
+                super::Ellipsis::End => (n, 0),
+            };
+            let head = line[..boundaries[head]].trim_end();
+            let tail = line[boundaries[num_chars - tail]..].trim_start();
+            let mut string = String::with_capacity(head.len() + ELLIPSIS.len() + tail.len());
+            string.push_str(head);
+            string.push_str(ELLIPSIS);
+            string.push_str(tail);
+            string
+        };
+
+        // Binary search for the greatest number of characters that still fit.
+        let (mut lo, mut hi) = (0, num_chars);
+        while lo < hi {
+            let mid = (lo + hi + 1) / 2;
+            if width(&shortened(mid), font, font_size) <= max_width {
+                lo = mid;
+            } else {
+                hi = mid - 1;
+            }
+        }
+
+        std::borrow::Cow::Owned(shortened(lo))
+    }
+
     /// Produce an `Infos` iterator wrapped by the given `next_break_fn`.
     pub fn infos_wrapped_by<'a, F>(text: &'a str,
                                    font: &'a super::Font,
@@ -1258,6 +1863,7 @@ pub mod line {
             font: font,
             font_size: font_size,
             max_width: max_width,
+            tab_stops: super::TabStops::default(),
             next_break_fn: next_break_fn,
             start_byte: 0,
             start_char: 0,
@@ -1271,17 +1877,19 @@ pub mod line {
     /// characters within the text (either `\n` or `\r\n`).
     pub fn infos<'a>(text: &'a str,
                      font: &'a super::Font,
-                     font_size: FontSize) -> Infos<'a, NextBreakFnPtr>
+                     font_size: FontSize) -> Infos<'a, NextBreakBy>
     {
-        fn no_wrap(text: &str,
-                   font: &super::Font,
-                   font_size: FontSize,
-                   _max_width: Scalar) -> (Break, Scalar)
-        {
-            next_break(text, font, font_size)
+        Infos {
+            text: text,
+            font: font,
+            font_size: font_size,
+            max_width: std::f64::MAX,
+            tab_stops: super::TabStops::default(),
+            next_break_fn: NextBreakBy::Newline,
+            start_byte: 0,
+            start_char: 0,
+            last_break: None,
         }
-
-        infos_wrapped_by(text, font, font_size, std::f64::MAX, no_wrap)
     }
 
     /// Produce an iterator yielding the bounding `Rect` for each line in the text.
@@ -1329,6 +1937,35 @@ pub mod line {
         }
     }
 
+    /// Produce an iterator yielding the bounding `Rect` for each line in the given slice of
+    /// `Info`s, beginning with the first line that is not entirely above the given `y` position.
+    ///
+    /// The `Rect`s are the same as those yielded by `rects` for the same arguments, however the
+    /// lines above `y` are skipped without stepping through them. This is useful for visiting
+    /// only the visible lines of a large block of text.
+    ///
+    /// Returns the index of the first yielded line. Every line preceding this index lies entirely
+    /// above `y`, however the first yielded line may also lie above `y`.
+    pub fn rects_below<'a>(infos: &'a [Info],
+                           font_size: FontSize,
+                           bounding_rect: Rect,
+                           x_align: super::Justify,
+                           y_align: Align,
+                           line_spacing: Scalar,
+                           y: Scalar) -> (usize, Rects<std::iter::Cloned<std::slice::Iter<'a, Info>>>)
+    {
+        let total_text_height = super::height(infos.len(), font_size, line_spacing);
+        let top = Range::new(0.0, total_text_height).align_to(y_align, bounding_rect.y).end;
+        let line_h = font_size as Scalar + line_spacing;
+        let first = ((top - font_size as Scalar - y) / line_h).floor();
+        let first = if first > 0.0 { std::cmp::min(first as usize, infos.len()) } else { 0 };
+        let infos = &infos[first..];
+        let first_top = top - first as Scalar * line_h;
+        let height = super::height(infos.len(), font_size, line_spacing);
+        let rect = Rect { x: bounding_rect.x, y: Range::new(first_top - height, first_top) };
+        (first, rects(infos.iter().cloned(), font_size, rect, x_align, Align::End, line_spacing))
+    }
+
     /// Produces an iterator yielding a `Rect` for the selected range in each selected line in a block
     /// of text.
     ///
@@ -1349,8 +1986,40 @@ pub mod line {
     }
 
 
-    impl<'a, F> Iterator for Infos<'a, F>
+    impl NextBreak for NextBreakBy {
+        fn next_break(&mut self,
+                      text: &str,
+                      font: &super::Font,
+                      font_size: FontSize,
+                      max_width: Scalar,
+                      tab_stops: super::TabStops) -> (Break, Scalar)
+        {
+            match *self {
+                NextBreakBy::Newline => next_break(text, font, font_size, tab_stops),
+                NextBreakBy::Character =>
+                    next_break_by_character(text, font, font_size, max_width, tab_stops),
+                NextBreakBy::Whitespace =>
+                    next_break_by_whitespace(text, font, font_size, max_width, tab_stops),
+            }
+        }
+    }
+
+    impl<F> NextBreak for F
         where F: for<'b> FnMut(&'b str, &'b super::Font, FontSize, Scalar) -> (Break, Scalar)
+    {
+        fn next_break(&mut self,
+                      text: &str,
+                      font: &super::Font,
+                      font_size: FontSize,
+                      max_width: Scalar,
+                      _tab_stops: super::TabStops) -> (Break, Scalar)
+        {
+            self(text, font, font_size, max_width)
+        }
+    }
+
+    impl<'a, F> Iterator for Infos<'a, F>
+        where F: NextBreak,
     {
         type Item = Info;
         fn next(&mut self) -> Option<Self::Item> {
@@ -1359,13 +2028,14 @@ pub mod line {
                 font,
                 font_size,
                 max_width,
+                tab_stops,
                 ref mut next_break_fn,
                 ref mut start_byte,
                 ref mut start_char,
                 ref mut last_break,
             } = *self;
 
-            match next_break_fn(&text[*start_byte..], font, font_size, max_width) {
+            match next_break_fn.next_break(&text[*start_byte..], font, font_size, max_width, tab_stops) {
                 (next @ Break::Newline { .. }, width) | (next @ Break::Wrap { .. }, width) => {
 
                     let next_break = match next {
@@ -1470,6 +2140,15 @@ pub mod line {
         }
     }
 
+    impl<'a, I> SelectedRects<'a, I> {
+        /// Lay out tab characters using the given `TabStops`.
+        pub fn tab_stops(mut self, tab_stops: super::TabStops) -> Self {
+            self.selected_char_rects_per_line =
+                self.selected_char_rects_per_line.tab_stops(tab_stops);
+            self
+        }
+    }
+
     impl<'a, I> Iterator for SelectedRects<'a, I>
         where I: Iterator<Item=(&'a str, Rect)>,
     {
@@ -1489,3 +2168,369 @@ pub mod line {
     }
 
 }
+
+
+/// A cache of text layouts, allowing the line infos and positioned glyphs of text that has not
+/// changed to be reused between updates.
+pub mod layout_cache {
+    use {FontSize, Scalar};
+    use fnv;
+    use std::collections::BTreeMap;
+    use std::hash::{Hash, Hasher};
+    use std::mem;
+    use std::sync::Arc;
+    use widget::primitive::text::Wrap;
+
+    /// The maximum number of layouts stored within a `LayoutCache` by default.
+    pub const DEFAULT_CAPACITY: usize = 1024;
+
+    /// The maximum number of bytes occupied by the layouts within a `LayoutCache` by default.
+    pub const DEFAULT_BYTE_CAPACITY: usize = 16 * 1024 * 1024;
+
+    /// Identifies the layout of some text by the text's content and the style used to lay it out.
+    #[derive(Copy, Clone, Debug)]
+    pub struct Key {
+        text_hash: u64,
+        font_id: super::font::Id,
+        font_size: FontSize,
+        maybe_wrap: Option<Wrap>,
+        max_width: Scalar,
+        justify: super::Justify,
+        tab_stops: super::TabStops,
+    }
+
+    /// The number of layouts that have been reused, produced and evicted by a `LayoutCache`.
+    #[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+    pub struct Stats {
+        /// The number of requests that were satisfied by a cached layout.
+        pub hits: u64,
+        /// The number of requests that required laying out the text.
+        pub misses: u64,
+        /// The number of layouts removed to make room for new ones.
+        pub evictions: u64,
+        /// The number of layouts currently stored.
+        pub len: usize,
+        /// The approximate number of bytes occupied by the stored layouts.
+        pub bytes: usize,
+    }
+
+    /// Stores the layout of recently used text, keyed by content and style.
+    ///
+    /// The cache is bounded both by the number of layouts and by the approximate number of bytes
+    /// they occupy, so that the layouts of large, frequently edited text (e.g. within a
+    /// `TextEdit`) cannot grow without bound. Once either limit is exceeded, the least recently
+    /// used layouts are evicted.
+    pub struct LayoutCache {
+        entries: fnv::FnvHashMap<Key, Entry>,
+        /// The key of each entry ordered by the time at which it was last used.
+        recency: BTreeMap<u64, Key>,
+        capacity: usize,
+        byte_capacity: usize,
+        bytes: usize,
+        /// Incremented on every request in order to track the least recently used entry.
+        clock: u64,
+        hits: u64,
+        misses: u64,
+        evictions: u64,
+    }
+
+    struct Entry {
+        /// The text is stored so that hash collisions never return the wrong layout.
+        text: String,
+        line_infos: Arc<[super::line::Info]>,
+        glyphs: Option<Glyphs>,
+        last_used: u64,
+        /// The approximate number of bytes occupied by the entry.
+        bytes: usize,
+    }
+
+    struct Glyphs {
+        dpi_factor: f32,
+        lines: Vec<Vec<super::PositionedGlyph>>,
+    }
+
+
+    impl Key {
+
+        /// Identify the layout of the given `text` with the given style.
+        ///
+        /// The `max_width` is only considered when the text is wrapped.
+        pub fn new(text: &str,
+                   font_id: super::font::Id,
+                   font_size: FontSize,
+                   maybe_wrap: Option<Wrap>,
+                   max_width: Scalar,
+                   justify: super::Justify,
+                   tab_stops: super::TabStops) -> Self
+        {
+            let mut hasher = fnv::FnvHasher::default();
+            text.hash(&mut hasher);
+            Key {
+                text_hash: hasher.finish(),
+                font_id: font_id,
+                font_size: font_size,
+                maybe_wrap: maybe_wrap,
+                max_width: if maybe_wrap.is_some() { max_width } else { 0.0 },
+                justify: justify,
+                tab_stops: tab_stops,
+            }
+        }
+
+        /// The id of the font with which the text is laid out.
+        pub fn font_id(&self) -> super::font::Id {
+            self.font_id
+        }
+
+        /// Produce the `line::Info` for each line of the given `text` laid out with this style.
+        pub fn line_infos<'a>(&self, text: &'a str, font: &'a super::Font)
+            -> super::line::Infos<'a, super::line::NextBreakBy>
+        {
+            let infos = super::line::infos(text, font, self.font_size).tab_stops(self.tab_stops);
+            match self.maybe_wrap {
+                None => infos,
+                Some(Wrap::Character) => infos.wrap_by_character(self.max_width),
+                Some(Wrap::Whitespace) => infos.wrap_by_whitespace(self.max_width),
+            }
+        }
+
+    }
+
+    impl PartialEq for Key {
+        fn eq(&self, other: &Self) -> bool {
+            self.text_hash == other.text_hash
+                && self.font_id == other.font_id
+                && self.font_size == other.font_size
+                && self.maybe_wrap == other.maybe_wrap
+                && self.max_width.to_bits() == other.max_width.to_bits()
+                && self.justify == other.justify
+                && self.tab_stops == other.tab_stops
+        }
+    }
+
+    impl Eq for Key {}
+
+    impl Hash for Key {
+        fn hash<H: Hasher>(&self, state: &mut H) {
+            self.text_hash.hash(state);
+            self.font_id.hash(state);
+            self.font_size.hash(state);
+            self.maybe_wrap.hash(state);
+            self.max_width.to_bits().hash(state);
+            self.justify.hash(state);
+            match self.tab_stops {
+                super::TabStops::Spaces(n) => (0u8, n).hash(state),
+                super::TabStops::Positions(positions) => {
+                    1u8.hash(state);
+                    for position in positions.iter() {
+                        position.to_bits().hash(state);
+                    }
+                },
+            }
+        }
+    }
+
+    impl LayoutCache {
+
+        /// An empty cache with the `DEFAULT_CAPACITY` and `DEFAULT_BYTE_CAPACITY`.
+        pub fn new() -> Self {
+            Self::with_capacity(DEFAULT_CAPACITY)
+        }
+
+        /// An empty cache that stores at most `capacity` layouts occupying at most the
+        /// `DEFAULT_BYTE_CAPACITY`.
+        pub fn with_capacity(capacity: usize) -> Self {
+            LayoutCache {
+                entries: fnv::FnvHashMap::default(),
+                recency: BTreeMap::new(),
+                capacity: capacity,
+                byte_capacity: DEFAULT_BYTE_CAPACITY,
+                bytes: 0,
+                clock: 0,
+                hits: 0,
+                misses: 0,
+                evictions: 0,
+            }
+        }
+
+        /// The maximum number of layouts stored by the cache.
+        pub fn capacity(&self) -> usize {
+            self.capacity
+        }
+
+        /// Set the maximum number of layouts stored by the cache, evicting the least recently used
+        /// layouts if necessary.
+        pub fn set_capacity(&mut self, capacity: usize) {
+            self.capacity = capacity;
+            self.evict_to_fit(None);
+        }
+
+        /// The maximum number of bytes occupied by the layouts stored within the cache.
+        pub fn byte_capacity(&self) -> usize {
+            self.byte_capacity
+        }
+
+        /// Set the maximum number of bytes occupied by the layouts stored within the cache,
+        /// evicting the least recently used layouts if necessary.
+        ///
+        /// The most recently used layout is always kept, even if it alone exceeds the limit.
+        pub fn set_byte_capacity(&mut self, byte_capacity: usize) {
+            self.byte_capacity = byte_capacity;
+            self.evict_to_fit(None);
+        }
+
+        /// Remove all layouts and reset the `Stats`.
+        pub fn clear(&mut self) {
+            self.entries.clear();
+            self.recency.clear();
+            self.bytes = 0;
+            self.hits = 0;
+            self.misses = 0;
+            self.evictions = 0;
+        }
+
+        /// The number of hits, misses and evictions since the cache was created or last cleared.
+        pub fn stats(&self) -> Stats {
+            Stats {
+                hits: self.hits,
+                misses: self.misses,
+                evictions: self.evictions,
+                len: self.entries.len(),
+                bytes: self.bytes,
+            }
+        }
+
+        /// The `line::Info` for each line of the given `text` laid out with the given `Key`.
+        ///
+        /// The returned handle shares the cached layout rather than copying it.
+        ///
+        /// The `font` must be the font identified by the `Key`.
+        pub fn line_infos(&mut self, key: &Key, text: &str, font: &super::Font)
+            -> Arc<[super::line::Info]>
+        {
+            self.entry(key, text, font).line_infos.clone()
+        }
+
+        /// The glyphs for each line of the given `text` laid out with the given `Key`.
+        ///
+        /// The glyphs of each line are positioned relative to the start of the line's baseline
+        /// and are scaled by the given `dpi_factor`.
+        pub fn line_glyphs(&mut self, key: &Key, text: &str, font: &super::Font, dpi_factor: f32)
+            -> &[Vec<super::PositionedGlyph>]
+        {
+            let added_bytes = {
+                let entry = self.entry(key, text, font);
+                let is_current = match entry.glyphs {
+                    Some(ref glyphs) => glyphs.dpi_factor == dpi_factor,
+                    None => false,
+                };
+                if is_current {
+                    None
+                } else {
+                    let scale = super::pt_to_scale((key.font_size as f32 * dpi_factor) as FontSize);
+                    let origin = super::rt::point(0.0, 0.0);
+                    let lines = entry.line_infos.iter()
+                        .map(|info| {
+                            let line = &text[info.byte_range()];
+                            super::layout(line, font, scale, origin, key.tab_stops)
+                                .dpi_factor(dpi_factor)
+                                .map(|g| g.standalone())
+                                .collect()
+                        })
+                        .collect();
+                    let old_bytes = entry.bytes;
+                    entry.glyphs = Some(Glyphs { dpi_factor: dpi_factor, lines: lines });
+                    entry.bytes = entry_bytes(entry);
+                    Some((old_bytes, entry.bytes))
+                }
+            };
+
+            // Laying out the glyphs may have pushed the cache over its byte capacity.
+            if let Some((old_bytes, new_bytes)) = added_bytes {
+                self.bytes = self.bytes - old_bytes + new_bytes;
+                self.evict_to_fit(Some(key));
+            }
+
+            let entry = self.entries.get(key).expect("entry was retrieved above");
+            &entry.glyphs.as_ref().expect("glyphs were laid out above").lines
+        }
+
+        /// Retrieve the entry for the given key, laying out the text if it is not yet cached.
+        fn entry(&mut self, key: &Key, text: &str, font: &super::Font) -> &mut Entry {
+            self.clock += 1;
+            let is_cached = match self.entries.get(key) {
+                Some(entry) => entry.text == text,
+                None => false,
+            };
+            if is_cached {
+                self.hits += 1;
+            } else {
+                self.misses += 1;
+                let mut entry = Entry {
+                    text: text.to_owned(),
+                    line_infos: key.line_infos(text, font).collect::<Vec<_>>().into(),
+                    glyphs: None,
+                    last_used: 0,
+                    bytes: 0,
+                };
+                entry.bytes = entry_bytes(&entry);
+                self.bytes += entry.bytes;
+                if let Some(old) = self.entries.insert(*key, entry) {
+                    self.bytes -= old.bytes;
+                    self.recency.remove(&old.last_used);
+                }
+            }
+
+            // Mark the entry as the most recently used.
+            let clock = self.clock;
+            let last_used = {
+                let entry = self.entries.get_mut(key).expect("entry was inserted above");
+                mem::replace(&mut entry.last_used, clock)
+            };
+            self.recency.remove(&last_used);
+            self.recency.insert(clock, *key);
+
+            if !is_cached {
+                self.evict_to_fit(Some(key));
+            }
+            self.entries.get_mut(key).expect("the most recently used entry is never evicted")
+        }
+
+        /// Evict the least recently used entries until the cache is within both its `capacity`
+        /// and `byte_capacity`, never evicting the entry with the given key.
+        fn evict_to_fit(&mut self, keep: Option<&Key>) {
+            while self.entries.len() > self.capacity || self.bytes > self.byte_capacity {
+                let oldest = match self.recency.iter().next() {
+                    Some((&last_used, key)) if Some(key) != keep => last_used,
+                    _ => break,
+                };
+                let key = self.recency.remove(&oldest).expect("oldest entry was found above");
+                if let Some(entry) = self.entries.remove(&key) {
+                    self.bytes -= entry.bytes;
+                    self.evictions += 1;
+                }
+            }
+        }
+
+    }
+
+    /// The approximate number of bytes occupied by the given entry.
+    fn entry_bytes(entry: &Entry) -> usize {
+        let glyphs = entry.glyphs.as_ref().map_or(0, |glyphs| {
+            glyphs.lines.iter()
+                .map(|line| line.len() * mem::size_of::<super::PositionedGlyph>())
+                .sum::<usize>()
+                + glyphs.lines.len() * mem::size_of::<Vec<super::PositionedGlyph>>()
+        });
+        mem::size_of::<Entry>()
+            + entry.text.len()
+            + entry.line_infos.len() * mem::size_of::<super::line::Info>()
+            + glyphs
+    }
+
+    impl Default for LayoutCache {
+        fn default() -> Self {
+            LayoutCache::new()
+        }
+    }
+
+}
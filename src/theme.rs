@@ -53,6 +53,15 @@ pub struct Theme {
     /// Once the `Duration` that separates two consecutive `Click`s is greater than this value, a
     /// `DoubleClick` event will no longer be generated.
     pub double_click_threshold: std::time::Duration,
+    /// The color of the ring drawn around the widget focused via the keyboard.
+    pub focus_color: Color,
+    /// The thickness of the ring drawn around the widget focused via the keyboard.
+    pub focus_width: Scalar,
+    /// How long the mouse must rest over a widget before its tooltip is displayed.
+    ///
+    /// This is measured by the `Ui`'s frame clock, so the delay only passes while the application
+    /// advances the clock via `Ui::advance` or `Input::Update`.
+    pub tooltip_delay: std::time::Duration,
 }
 
 /// The defaults for a specific widget.
@@ -103,6 +112,9 @@ impl Theme {
             widget_styling: fnv::FnvHashMap::default(),
             mouse_drag_threshold: 0.0,
             double_click_threshold: std::time::Duration::from_millis(500),
+            focus_color: Color::Rgba(0.3, 0.6, 1.0, 1.0),
+            focus_width: 2.0,
+            tooltip_delay: std::time::Duration::from_millis(600),
         }
     }
 
@@ -1,4 +1,8 @@
+use FontSize;
+use animation::{self, Tween};
+use clipboard::{self, Clipboard};
 use color::Color;
+use drag_and_drop;
 use event;
 use graph::{self, Graph};
 use input;
@@ -85,6 +89,50 @@ pub struct Ui {
     pending_scroll_events: Vec<event::Ui>,
     /// Mouse cursor
     mouse_cursor: cursor::MouseCursor,
+    /// The area occupied by the text cursor of the widget capturing the keyboard, if any.
+    ime_cursor_rect: Option<Rect>,
+    /// The layouts of recently displayed text, reused while the text and its style are unchanged.
+    ///
+    /// This is a `RefCell` so that text may be laid out while the `Ui` is borrowed immutably,
+    /// e.g. within `Ui::draw`.
+    text_layout_cache: std::cell::RefCell<text::layout_cache::LayoutCache>,
+    /// The clipboard used by widgets for copying and pasting text.
+    ///
+    /// This is a `RefCell` so that widgets may use the clipboard while handling their events.
+    clipboard: std::cell::RefCell<Box<Clipboard>>,
+    /// The number of seconds that the frame clock has advanced since the `Ui` was created.
+    time: Scalar,
+    /// The values being animated by widgets.
+    animations: animation::Animations,
+    /// The focusable widgets set during the current `set_widgets` stage, along with their
+    /// `tab_index`, in the order in which they were set.
+    focusable_widgets: Vec<(widget::Id, Option<u32>)>,
+    /// The order in which `Tab` moves the keyboard focus between widgets.
+    tab_order: Vec<widget::Id>,
+    /// The number of places by which the keyboard focus will be moved through the `tab_order` at
+    /// the end of the next `set_widgets` stage, i.e. the number of `Tab` presses minus the number
+    /// of `Shift+Tab` presses.
+    pending_focus_steps: isize,
+    /// Whether or not the keyboard focus was last moved via the keyboard, in which case a ring is
+    /// drawn around the focused widget.
+    focus_visible: bool,
+    /// The widget used to draw the ring around the focused widget.
+    focus_ring: widget::Id,
+    /// The drag-and-drop operation in progress, if any.
+    drag_and_drop: Option<drag_and_drop::Drag>,
+    /// The mouse buttons that have produced a `Drag` since they were last pressed, i.e. those
+    /// whose drag is cancelled by `Escape`.
+    dragging_buttons: fnv::FnvHashSet<input::MouseButton>,
+    /// The widgets set during the current `set_widgets` stage that have tooltips.
+    tooltips: fnv::FnvHashMap<widget::Id, widget::Tooltip>,
+    /// The text referred to by `TooltipText::Stage`s during the current `set_widgets` stage.
+    tooltip_texts: Vec<String>,
+    /// The widget whose tooltip is currently displayed, along with its tooltip.
+    tooltip: Option<(widget::Id, widget::Tooltip)>,
+    /// The box in which `Tooltip::Text`s are displayed.
+    tooltip_box: widget::Id,
+    /// The text of a displayed `Tooltip::Text`.
+    tooltip_text: widget::Id,
 
     // TODO: Remove the following fields as they should now be handled by `input::Global`.
 
@@ -184,6 +232,9 @@ impl Ui {
                         fnv::FnvBuildHasher::default())));
 
         let window = widget_graph.add_placeholder();
+        let focus_ring = widget_graph.add_placeholder();
+        let tooltip_box = widget_graph.add_placeholder();
+        let tooltip_text = widget_graph.add_placeholder();
         let prev_updated_widgets = updated_widgets.clone();
         Ui {
             widget_graph: widget_graph,
@@ -203,6 +254,23 @@ impl Ui {
             global_input: input::Global::new(),
             pending_scroll_events: Vec::new(),
             mouse_cursor: cursor::MouseCursor::Arrow,
+            ime_cursor_rect: None,
+            text_layout_cache: std::cell::RefCell::new(text::layout_cache::LayoutCache::new()),
+            clipboard: std::cell::RefCell::new(Box::new(clipboard::Memory::new())),
+            time: 0.0,
+            animations: animation::Animations::default(),
+            focusable_widgets: Vec::new(),
+            tab_order: Vec::new(),
+            pending_focus_steps: 0,
+            focus_visible: false,
+            focus_ring: focus_ring,
+            drag_and_drop: None,
+            dragging_buttons: fnv::FnvHashSet::default(),
+            tooltips: fnv::FnvHashMap::default(),
+            tooltip_texts: Vec::new(),
+            tooltip: None,
+            tooltip_box: tooltip_box,
+            tooltip_text: tooltip_text,
         }
     }
 
@@ -419,8 +487,12 @@ impl Ui {
                     let widget = self.global_input.current.widget_capturing_mouse;
                     let press_event = event::Ui::Press(widget, press).into();
                     self.global_input.push_event(press_event);
+                    self.dragging_buttons.remove(&mouse_button);
 
                     if let MouseButton::Left = mouse_button {
+                        // The keyboard focus is no longer being moved via the keyboard.
+                        self.focus_visible = false;
+
                         // Check to see if we need to uncapture the keyboard.
                         if let Some(idx) = self.global_input.current.widget_capturing_keyboard {
                             if Some(idx) != self.global_input.current.widget_under_mouse {
@@ -440,6 +512,10 @@ impl Ui {
                         }
                     }
 
+                    // Pressing a mouse button hides the tooltip until the mouse moves again.
+                    self.global_input.hover_start = None;
+                    self.hide_tooltip();
+
                     // Keep track of pressed buttons in the current input::State.
                     let xy = self.global_input.current.mouse.xy;
                     let widget = self.global_input.current.widget_under_mouse;
@@ -462,13 +538,54 @@ impl Ui {
                         self.global_input.current.modifiers.insert(modifier);
                     }
 
+                    // Move the keyboard focus through the tab order at the end of the next
+                    // `set_widgets` stage, unless the focused widget uses the `Tab` press itself.
+                    if let Key::Tab = key {
+                        let modifiers = self.global_input.current.modifiers;
+                        if !modifiers.contains(ModifierKey::CTRL)
+                        && !modifiers.contains(ModifierKey::ALT)
+                        && !modifiers.contains(ModifierKey::GUI) {
+                            self.pending_focus_steps +=
+                                if modifiers.contains(ModifierKey::SHIFT) { -1 } else { 1 };
+                        }
+                    }
+
                     // If `Esc` was pressed, check to see if we need to cancel a `Drag` or
                     // uncapture a widget.
+                    //
+                    // Buttons that are held without having been dragged are left alone so that
+                    // they may still produce a `Click`.
                     if let Key::Escape = key {
-                        // TODO:
-                        // 1. Cancel `Drag` if currently under way.
-                        // 2. If mouse is captured due to pinning widget with left mouse button,
-                        //    cancel capturing.
+                        self.finish_drag_and_drop(false);
+                        let buttons = self.global_input.current.mouse.buttons.clone();
+                        let mut cancelled_any = false;
+                        for (btn, btn_xy, widget) in buttons.pressed() {
+                            if !self.dragging_buttons.remove(&btn) {
+                                continue;
+                            }
+                            let event = event::Ui::DragCancelled(widget, event::DragCancelled {
+                                button: btn,
+                                origin: btn_xy,
+                                xy: self.global_input.current.mouse.xy,
+                                modifiers: self.global_input.current.modifiers,
+                            }).into();
+                            self.global_input.push_event(event);
+
+                            // Release the button so that it produces no further `Drag`s and no
+                            // `Click` once it is physically released.
+                            self.global_input.current.mouse.buttons.release(btn);
+                            cancelled_any = true;
+                        }
+
+                        // The widget was pinned by the pressed button, so release the mouse.
+                        if cancelled_any {
+                            if let Some(idx) = self.global_input.current.widget_capturing_mouse {
+                                let source = input::Source::Mouse;
+                                let event = event::Ui::WidgetUncapturesInputSource(idx, source);
+                                self.global_input.push_event(event.into());
+                                self.global_input.current.widget_capturing_mouse = None;
+                            }
+                        }
                     }
                 },
 
@@ -493,6 +610,7 @@ impl Ui {
                     let widget = self.global_input.current.widget_capturing_mouse;
                     let release_event = event::Ui::Release(widget, release).into();
                     self.global_input.push_event(release_event);
+                    self.dragging_buttons.remove(&mouse_button);
 
                     // Check for `Click` and `DoubleClick` events.
                     let down = self.global_input.current.mouse.buttons[mouse_button].if_down();
@@ -560,6 +678,11 @@ impl Ui {
                         }
                     }
 
+                    // Drop the drag-and-drop payload onto the widget under the mouse.
+                    if let MouseButton::Left = mouse_button {
+                        self.finish_drag_and_drop(true);
+                    }
+
                     // Uncapture widget capturing mouse if MouseButton::Left is down and
                     // widget_under_mouse != capturing widget.
                     if let MouseButton::Left = mouse_button {
@@ -631,6 +754,10 @@ impl Ui {
 
                     Motion::MouseCursor { x, y } => {
 
+                        // Moving the mouse hides the tooltip and restarts the hover delay.
+                        self.global_input.hover_start = Some(self.time);
+                        self.hide_tooltip();
+
                         // Check for drag events.
                         let last_mouse_xy = self.global_input.current.mouse.xy;
                         let mouse_xy = [x, y];
@@ -651,6 +778,7 @@ impl Ui {
                                     modifiers: self.global_input.current.modifiers,
                                 }).into();
                                 self.global_input.push_event(event);
+                                self.dragging_buttons.insert(btn);
                             }
                         }
 
@@ -659,6 +787,17 @@ impl Ui {
                         self.global_input.current.mouse.xy = mouse_xy;
 
                         self.track_widget_under_mouse_and_update_capturing();
+
+                        // Offer any drag-and-drop payload to the widget under the mouse.
+                        self.update_drag_and_drop_target();
+                        let target = self.drag_and_drop.as_ref().and_then(|drag| {
+                            if drag.is_finished() { None }
+                            else { drag.target().map(|target| (drag.source(), target)) }
+                        });
+                        if let Some((source, target)) = target {
+                            let event = self.drag_and_drop_event(source);
+                            self.global_input.push_event(event::Ui::DragOver(target, event).into());
+                        }
                     },
 
                     // Some scrolling occurred (e.g. mouse scroll wheel).
@@ -799,6 +938,13 @@ impl Ui {
                 self.global_input.push_event(text_event);
             },
 
+            Input::Ime(ime) => {
+                // Deliver the composition event to the widget capturing the keyboard.
+                let widget = self.global_input.current.widget_capturing_keyboard;
+                let ime_event = event::Ui::Ime(widget, ime).into();
+                self.global_input.push_event(ime_event);
+            },
+
             Input::Touch(touch) => match touch.phase {
 
                 input::touch::Phase::Start => {
@@ -902,9 +1048,235 @@ impl Ui {
             Input::Focus(_focused) => (),
 
             Input::Redraw => self.needs_redraw(),
+
+            Input::Update(secs) => self.advance(secs),
+        }
+    }
+
+    /// Advance the frame clock by the given number of seconds, progressing any animations.
+    ///
+    /// The `Ui` will need to be redrawn if any animations were running.
+    pub fn advance(&mut self, secs: Scalar) {
+        self.time += secs;
+        if self.animations.is_running() {
+            self.animations.advance(secs);
+            self.needs_redraw();
+        }
+    }
+
+    /// The number of seconds that the frame clock has advanced since the `Ui` was created.
+    pub fn time(&self) -> Scalar {
+        self.time
+    }
+
+    /// The focusable widgets in the order in which `Tab` moves the keyboard focus between them.
+    ///
+    /// Widgets with a `tab_index` come first in ascending order, followed by all other focusable
+    /// widgets in the order in which they were set during the last `set_widgets` stage.
+    pub fn tab_order(&self) -> &[widget::Id] {
+        &self.tab_order
+    }
+
+    /// Move the keyboard focus by the given number of places through the `tab_order`, wrapping
+    /// around at either end.
+    fn step_focus(&mut self, steps: isize) {
+        let len = self.tab_order.len() as isize;
+        if len == 0 {
+            return;
+        }
+        let focused = self.global_input.current.widget_capturing_keyboard
+            .and_then(|id| self.tab_order.iter().position(|&w| w == id));
+        let idx = match focused {
+            Some(idx) => idx as isize + steps,
+            // If no widget in the tab order is focused, `Tab` focuses the first.
+            None => if steps > 0 { steps - 1 } else { steps },
+        };
+        let idx = ((idx % len) + len) % len;
+        let id = self.tab_order[idx as usize];
+        self.capture_keyboard(Some(id));
+        self.focus_visible = true;
+        self.needs_redraw();
+    }
+
+    /// Give the keyboard capture to the given widget, producing the necessary events.
+    fn capture_keyboard(&mut self, id: Option<widget::Id>) {
+        let source = input::Source::Keyboard;
+        let capturing = self.global_input.current.widget_capturing_keyboard;
+        if capturing == id {
+            return;
+        }
+        if let Some(capturing) = capturing {
+            let event = event::Ui::WidgetUncapturesInputSource(capturing, source);
+            self.global_input.push_event(event.into());
+        }
+        if let Some(id) = id {
+            let event = event::Ui::WidgetCapturesInputSource(id, source);
+            self.global_input.push_event(event.into());
+        }
+        self.global_input.current.widget_capturing_keyboard = id;
+    }
+
+    /// The drag-and-drop operation in progress, if any.
+    ///
+    /// Once the payload has been dropped, the operation remains available until the end of the
+    /// following `set_widgets` stage so that the target may retrieve the payload.
+    pub fn drag_and_drop(&self) -> Option<&drag_and_drop::Drag> {
+        self.drag_and_drop.as_ref()
+    }
+
+    /// A drag-and-drop event for the given source describing the current state of the mouse.
+    fn drag_and_drop_event(&self, source: widget::Id) -> event::DragAndDrop {
+        event::DragAndDrop {
+            source: source,
+            xy: self.global_input.current.mouse.xy,
+            modifiers: self.global_input.current.modifiers,
+        }
+    }
+
+    /// Offer the drag-and-drop payload to the topmost widget under the mouse, skipping the drag
+    /// preview, producing `DragLeave` and `DragEnter` events if the target has changed.
+    fn update_drag_and_drop_target(&mut self) {
+        let (source, preview) = match self.drag_and_drop {
+            Some(ref drag) if !drag.is_finished() => (drag.source(), drag.preview()),
+            _ => return,
+        };
+        let target = {
+            let Ui { ref widget_graph, ref depth_order, ref theme, ref global_input, .. } = *self;
+            // Whether the widget is the preview or one of its children.
+            let is_preview = |mut id: widget::Id| loop {
+                if Some(id) == preview {
+                    return true;
+                }
+                match widget_graph.depth_parent(id) {
+                    Some(parent) => id = parent,
+                    None => return false,
+                }
+            };
+            let mut widgets = graph::algo::pick_widgets(&depth_order.indices,
+                                                        global_input.current.mouse.xy);
+            let mut target = None;
+            while let Some(id) = widgets.next(widget_graph, &depth_order.indices, theme) {
+                if !is_preview(id) {
+                    target = Some(id);
+                    break;
+                }
+            }
+            target
+        };
+        let event = self.drag_and_drop_event(source);
+        let previous = self.drag_and_drop.as_mut().and_then(|drag| drag.set_target(target));
+        if let Some(previous) = previous {
+            if let Some(previous) = previous {
+                self.global_input.push_event(event::Ui::DragLeave(previous, event).into());
+            }
+            if let Some(target) = target {
+                self.global_input.push_event(event::Ui::DragEnter(target, event).into());
+            }
+        }
+    }
+
+    /// End the drag-and-drop operation in progress, dropping the payload onto the target if
+    /// `drop_payload` is `true` and the target accepted the payload.
+    fn finish_drag_and_drop(&mut self, drop_payload: bool) {
+        let (source, target, accepted) = match self.drag_and_drop {
+            Some(ref mut drag) => {
+                if drag.is_finished() {
+                    return;
+                }
+                (drag.source(), drag.finish(), drag.is_accepted())
+            },
+            None => return,
+        };
+        let event = self.drag_and_drop_event(source);
+        if let Some(target) = target {
+            let event = if drop_payload && accepted {
+                event::Ui::Drop(target, event)
+            } else {
+                event::Ui::DragLeave(target, event)
+            };
+            self.global_input.push_event(event.into());
+        }
+        self.needs_redraw();
+    }
+
+    /// The widget whose tooltip is currently displayed, along with its tooltip.
+    ///
+    /// Widgets given a `Tooltip::Widget` should instantiate the tooltip widget while this yields
+    /// their tooltip.
+    pub fn tooltip(&self) -> Option<(widget::Id, &widget::Tooltip)> {
+        self.tooltip.as_ref().map(|&(id, ref tooltip)| (id, tooltip))
+    }
+
+    /// Whether or not the mouse is resting over a widget whose tooltip has yet to be displayed.
+    ///
+    /// While this is `true`, the application should continue to advance the frame clock and update
+    /// the widgets even when there are no other events so that the tooltip is displayed once the
+    /// `theme.tooltip_delay` has passed.
+    pub fn is_tooltip_pending(&self) -> bool {
+        self.tooltip.is_none() && self.tooltip_under_mouse().is_some()
+    }
+
+    /// A `Rect` of the given dimensions for displaying a tooltip.
+    ///
+    /// The `Rect` is placed beneath the mouse cursor, or above it if there is no room beneath,
+    /// and is kept within the bounds of the window.
+    pub fn tooltip_rect(&self, dim: Dimensions) -> Rect {
+        // The space left between the mouse position and the tooltip, leaving room for the cursor.
+        const CURSOR_MARGIN: Scalar = 16.0;
+        let mouse_xy = self.global_input.current.mouse.xy;
+        let (half_w, half_h) = (self.win_w / 2.0, self.win_h / 2.0);
+        let (half_tooltip_w, half_tooltip_h) = (dim[0] / 2.0, dim[1] / 2.0);
+        let below = mouse_xy[1] - CURSOR_MARGIN - half_tooltip_h;
+        let y = if below - half_tooltip_h >= -half_h {
+            below
+        } else {
+            mouse_xy[1] + CURSOR_MARGIN + half_tooltip_h
+        };
+        let x = (mouse_xy[0] + half_tooltip_w)
+            .min(half_w - half_tooltip_w)
+            .max(-half_w + half_tooltip_w);
+        let y = y.min(half_h - half_tooltip_h).max(-half_h + half_tooltip_h);
+        Rect::from_xy_dim([x, y], dim)
+    }
+
+    /// The tooltip of the widget under the mouse (or of its nearest parent with a tooltip) if the
+    /// mouse is resting with no buttons pressed.
+    fn tooltip_under_mouse(&self) -> Option<(widget::Id, widget::Tooltip)> {
+        if self.global_input.hover_start.is_none()
+        || self.global_input.current.mouse.buttons.pressed().next().is_some()
+        || self.drag_and_drop.is_some() {
+            return None;
+        }
+        let mut id = match self.global_input.current.widget_under_mouse {
+            Some(id) => id,
+            None => return None,
+        };
+        loop {
+            if let Some(tooltip) = self.tooltips.get(&id) {
+                return Some((id, tooltip.clone()));
+            }
+            match self.widget_graph.depth_parent(id) {
+                Some(parent) => id = parent,
+                None => return None,
+            }
+        }
+    }
+
+    /// Hide the displayed tooltip, if any.
+    fn hide_tooltip(&mut self) {
+        if self.tooltip.take().is_some() {
+            self.needs_redraw();
         }
     }
 
+    /// Whether or not any animated values have yet to reach their targets.
+    ///
+    /// While this is `true`, the application should continue to advance the frame clock and update
+    /// the widgets even when there are no other events.
+    pub fn is_animating(&self) -> bool {
+        self.animations.is_running()
+    }
+
 
     /// Get an immutable reference to global input. Handles aggregation of events and providing them to Widgets
     ///
@@ -1062,6 +1434,10 @@ impl Ui {
         ui_cell.ui.maybe_current_parent_id = Some(ui_cell.window.into());
 
         ui_cell.set_mouse_cursor(cursor::MouseCursor::Arrow);
+        ui_cell.set_ime_cursor_rect(None);
+        ui_cell.ui.focusable_widgets.clear();
+        ui_cell.ui.tooltips.clear();
+        ui_cell.ui.tooltip_texts.clear();
 
         ui_cell
     }
@@ -1101,6 +1477,7 @@ impl Ui {
             ref depth_order,
             ref theme,
             ref fonts,
+            ref text_layout_cache,
             win_w, win_h,
             ..
         } = *self;
@@ -1115,6 +1492,7 @@ impl Ui {
         }
 
         render::Primitives::new(widget_graph, indices, theme, fonts, [win_w, win_h])
+            .text_layout_cache(text_layout_cache)
     }
 
 
@@ -1155,10 +1533,127 @@ impl Ui {
         graph::algo::cropped_area_of_widget(&self.widget_graph, id)
     }
 
+    /// The `line::Info` for each line of the given `text` laid out with the style described by
+    /// the given `key`.
+    ///
+    /// The layout is retrieved from the `Ui`'s text layout cache if the same text has recently
+    /// been laid out with the same style, otherwise the text is laid out and cached.
+    ///
+    /// The returned handle is shared with the cache, so widgets may cheaply store it and check
+    /// whether their layout has changed via `Arc::ptr_eq` rather than comparing every line.
+    ///
+    /// Returns `None` if the font identified by the `key` does not exist.
+    pub fn text_line_infos(&self,
+                           key: &text::layout_cache::Key,
+                           text: &str) -> Option<std::sync::Arc<[text::line::Info]>>
+    {
+        self.fonts.get(key.font_id()).map(|font| {
+            self.text_layout_cache.borrow_mut().line_infos(key, text, font)
+        })
+    }
+
+    /// The dimensions of the given `text` when displayed by a `Text` widget with the given font,
+    /// size, wrapping, line spacing and tab stops.
+    ///
+    /// `max_width` is the width at which lines wrap and is ignored if `maybe_wrap` is `None`.
+    ///
+    /// This allows for sizing widgets to fit their text before they are instantiated. The layout
+    /// is retrieved from (or added to) the `Ui`'s text layout cache.
+    ///
+    /// Returns `[0.0, 0.0]` if there is no font for the given `font_id`.
+    pub fn measure_text(&self,
+                        text: &str,
+                        font_id: text::font::Id,
+                        font_size: FontSize,
+                        maybe_wrap: Option<widget::text::Wrap>,
+                        max_width: Scalar,
+                        line_spacing: Scalar,
+                        tab_stops: text::TabStops) -> Dimensions
+    {
+        let line_infos = self.measure_text_lines(text, font_id, font_size, maybe_wrap, max_width,
+                                                 tab_stops);
+        if line_infos.is_empty() {
+            return [0.0, 0.0];
+        }
+        let w = line_infos.iter().fold(0.0, |w, info| utils::partial_max(w, info.width));
+        let h = text::height(line_infos.len(), font_size, line_spacing);
+        [w, h]
+    }
+
+    /// The `line::Info` for each line of the given `text` when displayed by a `Text` widget with
+    /// the given font, size, wrapping and tab stops.
+    ///
+    /// Each `Info` describes the range of the line within the `text` along with its width.
+    ///
+    /// Returns an empty slice if there is no font for the given `font_id`.
+    pub fn measure_text_lines(&self,
+                              text: &str,
+                              font_id: text::font::Id,
+                              font_size: FontSize,
+                              maybe_wrap: Option<widget::text::Wrap>,
+                              max_width: Scalar,
+                              tab_stops: text::TabStops) -> std::sync::Arc<[text::line::Info]>
+    {
+        let key = text::layout_cache::Key::new(text, font_id, font_size, maybe_wrap, max_width,
+                                               text::Justify::Left, tab_stops);
+        self.text_line_infos(&key, text).unwrap_or_else(|| Vec::new().into())
+    }
+
+    /// The number of hits, misses and evictions of the `Ui`'s text layout cache along with the
+    /// number of layouts it currently stores.
+    pub fn text_layout_cache_stats(&self) -> text::layout_cache::Stats {
+        self.text_layout_cache.borrow().stats()
+    }
+
+    /// Set the maximum number of text layouts cached by the `Ui`.
+    ///
+    /// By default, this is `text::layout_cache::DEFAULT_CAPACITY`.
+    pub fn set_text_layout_cache_capacity(&mut self, capacity: usize) {
+        self.text_layout_cache.borrow_mut().set_capacity(capacity);
+    }
+
+    /// Set the maximum number of bytes occupied by the text layouts cached by the `Ui`.
+    ///
+    /// By default, this is `text::layout_cache::DEFAULT_BYTE_CAPACITY`.
+    pub fn set_text_layout_cache_byte_capacity(&mut self, byte_capacity: usize) {
+        self.text_layout_cache.borrow_mut().set_byte_capacity(byte_capacity);
+    }
+
+    /// Remove all cached text layouts and reset the cache's statistics.
+    pub fn clear_text_layout_cache(&mut self) {
+        self.text_layout_cache.borrow_mut().clear();
+    }
+
+    /// Install the clipboard used by widgets for copying and pasting text.
+    ///
+    /// By default, the `Ui` uses an in-memory `clipboard::Memory`.
+    pub fn set_clipboard<C>(&mut self, clipboard: C)
+        where C: Clipboard + 'static,
+    {
+        self.clipboard = std::cell::RefCell::new(Box::new(clipboard));
+    }
+
+    /// The text currently held by the `Ui`'s clipboard, if any.
+    pub fn clipboard_contents(&self) -> Option<String> {
+        self.clipboard.borrow_mut().get_contents()
+    }
+
+    /// Replace the contents of the `Ui`'s clipboard with the given text.
+    pub fn set_clipboard_contents(&self, contents: String) {
+        self.clipboard.borrow_mut().set_contents(contents);
+    }
+
     /// Get mouse cursor state.
     pub fn mouse_cursor(&self) -> cursor::MouseCursor {
         self.mouse_cursor
     }
+
+    /// The area occupied by the text cursor of the widget capturing the keyboard, if it has one.
+    ///
+    /// Backends may use this to position the candidate window of an input method editor (IME).
+    pub fn ime_cursor_rect(&self) -> Option<Rect> {
+        self.ime_cursor_rect
+    }
 }
 
 
@@ -1227,10 +1722,195 @@ impl<'a> UiCell<'a> {
     pub fn set_mouse_cursor(&mut self, cursor: cursor::MouseCursor) {
         self.ui.mouse_cursor = cursor;
     }
+
+    /// Sets the area occupied by the text cursor of the widget capturing the keyboard.
+    ///
+    /// This is reset to `None` at the beginning of each call to `Ui::set_widgets`.
+    pub fn set_ime_cursor_rect(&mut self, rect: Option<Rect>) {
+        self.ui.ime_cursor_rect = rect;
+    }
+
+    /// Prevent the `Tab` presses received since the previous `set_widgets` stage from moving the
+    /// keyboard focus.
+    ///
+    /// This is useful for widgets that respond to `Tab` themselves while focused.
+    pub fn cancel_focus_traversal(&mut self) {
+        self.ui.pending_focus_steps = 0;
+    }
+
+    /// Begin dragging the given `payload` from the `source` widget.
+    ///
+    /// This is usually called upon the `source` receiving a left mouse `Drag`. The drag ends when
+    /// the left mouse button is released or `Escape` is pressed. Until then, the widget under the
+    /// cursor receives `DragEnter`, `DragOver` and `DragLeave` events and may accept the payload
+    /// via `accept_drop`.
+    ///
+    /// The `preview` widget, if any, is excluded from receiving the payload so that it may be
+    /// positioned beneath the cursor. This has no effect if the left mouse button is up or another
+    /// drag is already in progress.
+    pub fn start_drag<T>(&mut self, source: widget::Id, payload: T, preview: Option<widget::Id>)
+        where T: std::any::Any + Send,
+    {
+        if self.ui.global_input.current.mouse.buttons.left().is_up() {
+            return;
+        }
+        if let Some(ref drag) = self.ui.drag_and_drop {
+            if !drag.is_finished() {
+                return;
+            }
+        }
+        let drag = drag_and_drop::Drag::new(source, Box::new(payload), preview);
+        self.ui.drag_and_drop = Some(drag);
+        self.ui.needs_redraw();
+    }
+
+    /// Accept the drag-and-drop payload on behalf of the given `target`, so that it receives a
+    /// `Drop` event if the payload is released over it.
+    ///
+    /// This has no effect unless the `target` is the widget currently being offered the payload.
+    /// The payload is rejected by default each time it is moved onto a new target.
+    pub fn accept_drop(&mut self, target: widget::Id) {
+        if let Some(ref mut drag) = self.ui.drag_and_drop {
+            drag.set_accepted(target, true);
+        }
+    }
+
+    /// Reject the drag-and-drop payload on behalf of the given `target`, undoing `accept_drop`.
+    pub fn reject_drop(&mut self, target: widget::Id) {
+        if let Some(ref mut drag) = self.ui.drag_and_drop {
+            drag.set_accepted(target, false);
+        }
+    }
+
+    /// Store the given tooltip text for the current `set_widgets` stage, producing the
+    /// `TooltipText` that may be given to a widget's `tooltip` builder method.
+    ///
+    /// This allows tooltips to be formatted or localised at runtime. The produced `TooltipText`
+    /// only refers to the given text during the current stage.
+    pub fn tooltip_text<S>(&mut self, text: S) -> widget::TooltipText
+        where S: Into<String>,
+    {
+        self.ui.tooltip_texts.push(text.into());
+        widget::TooltipText::Stage(self.ui.tooltip_texts.len() - 1)
+    }
+
+    /// Display the given tooltip text for the `owner` widget beneath the mouse.
+    fn display_tooltip_text(&mut self, owner: widget::Id, text: &str) {
+        use {Borderable, Colorable, Positionable, Sizeable};
+
+        // The space between the text and the edges of the box.
+        const PAD: Scalar = 4.0;
+
+        let font_id = match self.ui.theme.font_id.or_else(|| self.ui.fonts.ids().next()) {
+            Some(font_id) => font_id,
+            None => return,
+        };
+        if self.ui.fonts.get(font_id).is_none() {
+            return;
+        }
+        let font_size = self.ui.theme.font_size_small;
+        let style = widget::text::Style::default();
+        let line_spacing = style.line_spacing(&self.ui.theme);
+        let tab_stops = style.tab_stops(&self.ui.theme);
+        let text_dim = self.ui.measure_text(text, font_id, font_size, None, std::f64::MAX,
+                                            line_spacing, tab_stops);
+        let rect = self.ui.tooltip_rect([text_dim[0] + PAD * 2.0, text_dim[1] + PAD * 2.0]);
+        let Theme { shape_color, border_color, border_width, label_color, .. } = self.ui.theme;
+        let (tooltip_box, tooltip_text, window) =
+            (self.ui.tooltip_box, self.ui.tooltip_text, self.ui.window);
+
+        widget::BorderedRectangle::new(rect.dim())
+            .xy(rect.xy())
+            .color(shape_color)
+            .border(border_width)
+            .border_color(border_color)
+            .parent(window)
+            .floating(true)
+            .graphics_for(owner)
+            .set(tooltip_box, self);
+
+        widget::Text::new(text)
+            .font_id(font_id)
+            .font_size(font_size)
+            .color(label_color)
+            .no_line_wrap()
+            .left_justify()
+            .line_spacing(line_spacing)
+            .tab_stops(tab_stops)
+            .wh(text_dim)
+            .middle_of(tooltip_box)
+            .parent(tooltip_box)
+            .graphics_for(owner)
+            .set(tooltip_text, self);
+    }
+
+    /// Produce the current value of the animation with the given `key` for the given widget.
+    ///
+    /// When the `target` differs from that of the previous call, the value begins animating from
+    /// its current value towards the new `target` over the given `duration` in seconds. The first
+    /// time a value is animated, it begins at its `target`.
+    ///
+    /// The `Ui` will be redrawn while the value has yet to reach its target. Animations belonging to
+    /// widgets that are no longer updated are discarded at the end of `Ui::set_widgets`.
+    pub fn animate<T>(&mut self,
+                      widget: widget::Id,
+                      key: &'static str,
+                      target: T,
+                      duration: Scalar,
+                      easing: animation::Easing) -> T
+        where T: Tween,
+    {
+        let value = self.ui.animations.animate(widget, key, target, duration, easing);
+        if value.to_components() != target.to_components() {
+            self.ui.needs_redraw();
+        }
+        value
+    }
 }
 
 impl<'a> Drop for UiCell<'a> {
     fn drop(&mut self) {
+        // Draw a ring around the focused widget if the focus was moved via the keyboard.
+        let focused = self.ui.global_input.current.widget_capturing_keyboard;
+        if let (true, Some(focused)) = (self.ui.focus_visible, focused) {
+            if self.ui.updated_widgets.contains(&focused) {
+                if let Some(rect) = self.ui.rect_of(focused) {
+                    use {Colorable, Positionable};
+                    let width = self.ui.theme.focus_width;
+                    let rect = rect.pad(-width);
+                    let line_style = widget::line::Style::solid().thickness(width);
+                    let color = self.ui.theme.focus_color;
+                    let (focus_ring, window) = (self.ui.focus_ring, self.ui.window);
+                    widget::Rectangle::outline_styled(rect.dim(), line_style)
+                        .xy(rect.xy())
+                        .color(color)
+                        .parent(window)
+                        .floating(true)
+                        .graphics_for(focused)
+                        .set(focus_ring, self);
+                }
+            }
+        }
+        // Display the tooltip of the widget under the mouse once the mouse has rested over it.
+        let delay = self.ui.theme.tooltip_delay;
+        let delay = delay.as_secs() as Scalar + delay.subsec_nanos() as Scalar / 1_000_000_000.0;
+        let rested = self.ui.global_input.hover_start
+            .map_or(false, |start| self.ui.time - start >= delay);
+        if rested && self.ui.tooltip.is_none() {
+            self.ui.tooltip = self.ui.tooltip_under_mouse();
+        }
+        // Refresh the displayed tooltip from the current stage so that its text stays up to date.
+        if let Some(owner) = self.ui.tooltip.as_ref().map(|&(owner, _)| owner) {
+            let tooltip = match self.ui.tooltips.get(&owner) {
+                Some(tooltip) if self.ui.updated_widgets.contains(&owner) => Some(tooltip.clone()),
+                _ => None,
+            };
+            if let Some(widget::Tooltip::Text(ref text)) = tooltip {
+                self.display_tooltip_text(owner, text);
+            }
+            self.ui.tooltip = tooltip.map(|tooltip| (owner, tooltip));
+        }
+
         // We'll need to re-draw if we have gained or lost widgets.
         let changed = self.ui.updated_widgets != self.ui.prev_updated_widgets;
         if changed {
@@ -1250,9 +1930,45 @@ impl<'a> Drop for UiCell<'a> {
             depth_order.update(widget_graph, window, updated_widgets);
         }
 
+        // Draw the displayed tooltip over all other widgets.
+        if let Some((_, ref tooltip)) = self.ui.tooltip {
+            let id = match *tooltip {
+                widget::Tooltip::Text(_) => self.ui.tooltip_box,
+                widget::Tooltip::Widget(id) => id,
+            };
+            let Ui { ref widget_graph, ref mut depth_order, ref updated_widgets, .. } = *self.ui;
+            if updated_widgets.contains(&id) {
+                depth_order.bring_to_front(widget_graph, id);
+            }
+        }
+
+        // Discard the animations of widgets that were not updated.
+        {
+            let Ui { ref mut animations, ref updated_widgets, .. } = *self.ui;
+            animations.retain(|widget| updated_widgets.contains(&widget));
+        }
+
         // Reset the global input state. Note that this is the **only** time this should be called.
         self.ui.global_input.clear_events_and_update_start_state();
 
+        // Discard a drag-and-drop operation once its payload has been delivered, otherwise offer
+        // the payload to whichever widget is now under the mouse.
+        if self.ui.drag_and_drop.as_ref().map_or(false, |drag| drag.is_finished()) {
+            self.ui.drag_and_drop = None;
+        }
+        self.ui.update_drag_and_drop_target();
+
+        // Order the focusable widgets, moving the keyboard focus for any `Tab` presses.
+        {
+            let mut focusable_widgets = self.ui.focusable_widgets.clone();
+            focusable_widgets.sort_by_key(|&(_, tab_index)| (tab_index.is_none(), tab_index));
+            self.ui.tab_order = focusable_widgets.into_iter().map(|(id, _)| id).collect();
+        }
+        let focus_steps = std::mem::replace(&mut self.ui.pending_focus_steps, 0);
+        if focus_steps != 0 {
+            self.ui.step_focus(focus_steps);
+        }
+
         // Update which widget is under the cursor.
         if changed {
             self.ui.track_widget_under_mouse_and_update_capturing();
@@ -1328,6 +2044,30 @@ pub fn infer_parent_unchecked(ui: &Ui, x_pos: Position, y_pos: Position) -> widg
 }
 
 
+/// Register the tooltip of the given widget for the current `set_widgets` stage.
+pub fn push_tooltip(ui: &mut Ui, id: widget::Id, tooltip: widget::Tooltip) {
+    ui.tooltips.insert(id, tooltip);
+}
+
+/// Register the tooltip text of the given widget for the current `set_widgets` stage.
+///
+/// `TooltipText::Stage`s that do not refer to text stored during the current stage are ignored.
+pub fn push_tooltip_text(ui: &mut Ui, id: widget::Id, text: widget::TooltipText) {
+    let text = match text {
+        widget::TooltipText::Static(text) => text.to_string(),
+        widget::TooltipText::Stage(index) => match ui.tooltip_texts.get(index) {
+            Some(text) => text.clone(),
+            None => return,
+        },
+    };
+    push_tooltip(ui, id, widget::Tooltip::Text(text));
+}
+
+/// Add the given focusable widget to the tab order of the current `set_widgets` stage.
+pub fn push_focusable_widget(ui: &mut Ui, id: widget::Id, tab_index: Option<u32>) {
+    ui.focusable_widgets.push((id, tab_index));
+}
+
 /// Cache some `PreUpdateCache` widget data into the widget graph.
 /// Set the widget that is being cached as the new `prev_widget`.
 /// Set the widget's parent as the new `current_parent`.
@@ -2,6 +2,7 @@
 
 use {Color, Colorable, FontSize, Borderable, Labelable, Positionable, Sizeable, UiCell, Widget};
 use image;
+use input;
 use position::{self, Align, Rect, Scalar};
 use text;
 use widget;
@@ -51,6 +52,9 @@ pub struct Style {
     /// The position of the title bar's `Label` widget over the *y* axis.
     #[conrod(default = "position::Relative::Align(Align::Middle)")]
     pub label_y: Option<position::Relative>,
+    /// How the label is displayed when it is wider than the Button.
+    #[conrod(default = "widget::text::Overflow::Ellipsis(text::Ellipsis::End)")]
+    pub label_overflow: Option<widget::text::Overflow>,
 }
 
 widget_ids! {
@@ -275,6 +279,14 @@ impl<'a, S> Button<'a, S> {
         self
     }
 
+    /// Specify how the label is displayed when it is wider than the `Button`.
+    ///
+    /// By default, the end of the label is replaced with "…".
+    pub fn label_overflow(mut self, overflow: widget::text::Overflow) -> Self {
+        self.style.label_overflow = Some(overflow);
+        self
+    }
+
     builder_methods!{
         pub enabled { enabled = bool }
     }
@@ -294,6 +306,10 @@ impl<'a> Widget for Button<'a, Flat> {
         self.style.clone()
     }
 
+    fn is_focusable(&self) -> bool {
+        true
+    }
+
     /// Update the state of the Button.
     fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
         let widget::UpdateArgs { id, state, style, rect, ui, .. } = args;
@@ -312,7 +328,8 @@ impl<'a> Widget for Button<'a, Flat> {
 
         // Label widget.
         if let Some(l) = maybe_label {
-            label(id, state.label, l, style, ui);
+            let max_w = rect.w() - style.border(&ui.theme) * 2.0;
+            label(id, state.label, l, max_w, style, ui);
         }
 
         TimesClicked(times_triggered)
@@ -333,6 +350,10 @@ impl<'a> Widget for Button<'a, Image> {
         self.style.clone()
     }
 
+    fn is_focusable(&self) -> bool {
+        true
+    }
+
     /// Update the state of the Button.
     fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
         let widget::UpdateArgs { id, state, style, rect, ui, .. } = args;
@@ -372,7 +393,7 @@ impl<'a> Widget for Button<'a, Image> {
         image.set(state.image, ui);
 
         if let Some(s) = maybe_label {
-            label(id, state.label, s, style, ui);
+            label(id, state.label, s, rect.w(), style, ui);
         }
 
         TimesClicked(times_triggered)
@@ -390,7 +411,11 @@ fn interaction_and_times_triggered(button_id: widget::Id, ui: &UiCell) -> (Inter
                  .any(|t| t.start.widget == Some(button_id));
         if is_pressed { Interaction::Press } else { Interaction::Hover }
     });
-    let times_triggered = (input.clicks().left().count() + input.taps().count()) as u16;
+    // While focused, `Space` and `Return` also trigger the button.
+    let key_presses = input.presses().key()
+        .filter(|press| press.key == input::Key::Space || press.key == input::Key::Return)
+        .count();
+    let times_triggered = (input.clicks().left().count() + input.taps().count() + key_presses) as u16;
     (interaction, times_triggered)
 }
 
@@ -411,7 +436,7 @@ fn bordered_rectangle(button_id: widget::Id, rectangle_id: widget::Id,
 }
 
 fn label(button_id: widget::Id, label_id: widget::Id,
-         label: &str, style: &Style, ui: &mut UiCell)
+         label: &str, max_w: Scalar, style: &Style, ui: &mut UiCell)
 {
     let color = style.label_color(&ui.theme);
     let font_size = style.label_font_size(&ui.theme);
@@ -419,8 +444,24 @@ fn label(button_id: widget::Id, label_id: widget::Id,
     let y = style.label_y(&ui.theme);
     let justify = style.label_justify(&ui.theme);
     let font_id = style.label_font_id(&ui.theme).or(ui.fonts.ids().next());
+
+    // If the label is wider than the button, shorten it with an ellipsis.
+    let maybe_ellipsis = match style.label_overflow(&ui.theme) {
+        widget::text::Overflow::Ellipsis(ellipsis) => {
+            let label_w = font_id
+                .and_then(|id| ui.fonts.get(id))
+                .map(|font| label.lines()
+                    .map(|line| text::line::width(line, font, font_size))
+                    .fold(0.0, Scalar::max))
+                .unwrap_or(0.0);
+            if label_w > max_w { Some((max_w.max(0.0), ellipsis)) } else { None }
+        },
+        widget::text::Overflow::Visible => None,
+    };
+
     widget::Text::new(label)
         .and_then(font_id, widget::Text::font_id)
+        .and_then(maybe_ellipsis, |label, (w, ellipsis)| label.w(w).ellipsis(ellipsis))
         .x_position_relative_to(button_id, x)
         .y_position_relative_to(button_id, y)
         .justify(justify)
@@ -0,0 +1,348 @@
+//! A widget for editing source code, built upon the `TextEdit` widget.
+
+use {Borderable, Color, Colorable, FontSize, Positionable, Sizeable, Widget};
+use position::{Align, Range, Rect, Scalar};
+use std;
+use text;
+use widget;
+use widget::primitive::text::Wrap;
+use widget::text_edit;
+
+
+/// A widget for editing source code.
+///
+/// The text is displayed within a vertically scrollable area alongside a gutter of line numbers.
+/// The line containing the cursor is highlighted, each new line begins with the indentation of the
+/// line before it and the text may be colored via a highlighting function.
+///
+/// Lines are wrapped by character at the width of the editing area.
+#[derive(WidgetCommon_)]
+pub struct CodeEdit<'a> {
+    #[conrod(common_builder)]
+    common: widget::CommonBuilder,
+    text: &'a str,
+    style: Style,
+    highlight: Option<&'a Fn(&str) -> Vec<(std::ops::Range<usize>, Color)>>,
+}
+
+/// Unique graphical styling for the CodeEdit.
+#[derive(Copy, Clone, Debug, Default, PartialEq, WidgetStyle_)]
+pub struct Style {
+    /// The color of the text (this includes cursor and selection color).
+    #[conrod(default = "theme.label_color")]
+    pub color: Option<Color>,
+    /// The color of the area behind the text.
+    #[conrod(default = "theme.background_color")]
+    pub background_color: Option<Color>,
+    /// The font size for the text and line numbers.
+    #[conrod(default = "theme.font_size_medium")]
+    pub font_size: Option<FontSize>,
+    /// The font used for the text and line numbers.
+    #[conrod(default = "theme.font_id")]
+    pub font_id: Option<Option<text::font::Id>>,
+    /// The vertical space between each line of text.
+    #[conrod(default = "1.0")]
+    pub line_spacing: Option<Scalar>,
+    /// The positions to which tab characters advance.
+    #[conrod(default = "text::TabStops::default()")]
+    pub tab_stops: Option<text::TabStops>,
+    /// The color of the gutter in which line numbers are displayed.
+    #[conrod(default = "theme.shape_color")]
+    pub gutter_color: Option<Color>,
+    /// The space between the line numbers and the edges of the gutter.
+    #[conrod(default = "4.0")]
+    pub gutter_padding: Option<Scalar>,
+    /// The color of the line numbers.
+    #[conrod(default = "theme.label_color.alpha(0.5)")]
+    pub line_number_color: Option<Color>,
+    /// The color of the highlight behind the line containing the cursor.
+    #[conrod(default = "theme.label_color.alpha(0.08)")]
+    pub current_line_color: Option<Color>,
+}
+
+widget_ids! {
+    struct Ids {
+        canvas,
+        gutter,
+        line_numbers[],
+        current_line,
+        text_edit,
+    }
+}
+
+/// The State of the CodeEdit widget that will be cached within the Ui.
+pub struct State {
+    ids: Ids,
+    /// The text as of the most recent update, used to detect when the text changes.
+    text: String,
+    /// The byte index at which each newline-separated line of the `text` begins.
+    line_starts: Vec<usize>,
+    /// The wrapping that produced the `num_wrapped_lines`, or `None` if the text has changed since.
+    wrapping: Option<Wrapping>,
+    /// The number of lines of the `text` once wrapped at the width of the editing area.
+    num_wrapped_lines: usize,
+}
+
+/// The properties that determine how the text is wrapped into lines.
+#[derive(Clone, Debug, PartialEq)]
+struct Wrapping {
+    font_id: text::font::Id,
+    font_size: FontSize,
+    tab_stops: text::TabStops,
+    w: Scalar,
+}
+
+impl<'a> CodeEdit<'a> {
+
+    /// Construct a CodeEdit widget.
+    pub fn new(text: &'a str) -> Self {
+        CodeEdit {
+            common: widget::CommonBuilder::default(),
+            style: Style::default(),
+            text: text,
+            highlight: None,
+        }
+    }
+
+    /// Specify the font used for displaying the text.
+    pub fn font_id(mut self, font_id: text::font::Id) -> Self {
+        self.style.font_id = Some(Some(font_id));
+        self
+    }
+
+    /// Color the text using the given function.
+    ///
+    /// See `TextEdit::highlight` for details.
+    pub fn highlight(mut self, highlight: &'a Fn(&str) -> Vec<(std::ops::Range<usize>, Color)>) -> Self {
+        self.highlight = Some(highlight);
+        self
+    }
+
+    builder_methods!{
+        pub background_color { style.background_color = Some(Color) }
+        pub font_size { style.font_size = Some(FontSize) }
+        pub line_spacing { style.line_spacing = Some(Scalar) }
+        pub tab_stops { style.tab_stops = Some(text::TabStops) }
+        pub gutter_color { style.gutter_color = Some(Color) }
+        pub gutter_padding { style.gutter_padding = Some(Scalar) }
+        pub line_number_color { style.line_number_color = Some(Color) }
+        pub current_line_color { style.current_line_color = Some(Color) }
+    }
+
+}
+
+impl<'a> Widget for CodeEdit<'a> {
+    type State = State;
+    type Style = Style;
+    type Event = Vec<text_edit::Event>;
+
+    fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
+        State {
+            ids: Ids::new(id_gen),
+            text: String::new(),
+            line_starts: vec![0],
+            wrapping: None,
+            num_wrapped_lines: 1,
+        }
+    }
+
+    fn style(&self) -> Self::Style {
+        self.style.clone()
+    }
+
+    /// Update the state of the CodeEdit.
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        let widget::UpdateArgs { id, state, rect, style, ui, .. } = args;
+        let CodeEdit { text, highlight, .. } = self;
+
+        // If we've no font to use for text logic, bail out without updating.
+        let font_id = match style.font_id(&ui.theme)
+            .or(ui.fonts.ids().next())
+            .and_then(|id| ui.fonts.get(id).map(|_| id))
+        {
+            Some(font_id) => font_id,
+            None => return Vec::new(),
+        };
+
+        let color = style.color(ui.theme());
+        let font_size = style.font_size(ui.theme());
+        let line_spacing = style.line_spacing(ui.theme());
+        let tab_stops = style.tab_stops(ui.theme());
+        let gutter_padding = style.gutter_padding(ui.theme());
+
+        // Only find the start of each line when the text changes.
+        if state.text != text {
+            state.update(|state| {
+                state.text = text.to_string();
+                state.line_starts = line_starts(text);
+                state.wrapping = None;
+            });
+        }
+
+        // The gutter must be wide enough to fit the number of the last line.
+        let gutter_w = {
+            let num_lines = state.line_starts.len();
+            let widest_number: String = num_lines.to_string().chars().map(|_| '0').collect();
+            let font = ui.fonts.get(font_id).unwrap();
+            text::line::width(&widest_number, font, font_size) + gutter_padding * 2.0
+        };
+        let text_w = (rect.w() - gutter_w).max(0.0);
+
+        // The height of the `TextEdit`, using the same layout as the `TextEdit` itself so that the
+        // lines are retrieved from the `Ui`'s layout cache. The lines are only counted when the
+        // text or its wrapping changes.
+        let wrapping = Wrapping {
+            font_id: font_id,
+            font_size: font_size,
+            tab_stops: tab_stops.clone(),
+            w: text_w,
+        };
+        if state.wrapping.as_ref() != Some(&wrapping) {
+            let key = text::layout_cache::Key::new(text, font_id, font_size,
+                                                   Some(Wrap::Character), text_w,
+                                                   text::Justify::Left, tab_stops);
+            let num_lines = ui.text_line_infos(&key, text).map_or(1, |infos| infos.len());
+            state.update(|state| {
+                state.wrapping = Some(wrapping);
+                state.num_wrapped_lines = num_lines;
+            });
+        }
+        let text_h = text::height(state.num_wrapped_lines, font_size, line_spacing).max(rect.h());
+
+        widget::Canvas::new()
+            .wh_of(id)
+            .middle_of(id)
+            .scroll_kids_vertically()
+            .crop_kids()
+            .pad(0.0)
+            .border(0.0)
+            .color(style.background_color(ui.theme()))
+            .parent(id)
+            .set(state.ids.canvas, ui);
+
+        let events = widget::TextEdit::new(text)
+            .and_then(highlight, widget::TextEdit::highlight)
+            .font_id(font_id)
+            .font_size(font_size)
+            .color(color)
+            .line_spacing(line_spacing)
+            .tab_stops(tab_stops)
+            .wrap_by_character()
+            .left_justify()
+            .align_text_top()
+            .restrict_to_height(false)
+            .auto_indent(true)
+            .w_h(text_w, text_h)
+            .top_left_with_margins_on(state.ids.canvas, 0.0, gutter_w)
+            .parent(state.ids.canvas)
+            .set(state.ids.text_edit, ui);
+
+        widget::Rectangle::fill([gutter_w, text_h])
+            .top_left_of(state.ids.canvas)
+            .color(style.gutter_color(ui.theme()))
+            .parent(state.ids.canvas)
+            .graphics_for(id)
+            .set(state.ids.gutter, ui);
+
+        let (text_rect, visible) = match (ui.rect_of(state.ids.text_edit), ui.rect_of(state.ids.canvas)) {
+            (Some(text_rect), Some(visible)) => (text_rect, visible),
+            _ => return events,
+        };
+        let is_editing = ui.global_input().current.widget_capturing_keyboard == Some(state.ids.text_edit);
+
+        // Retrieve the layout of the text and the cursor position from the `TextEdit`, from which
+        // the visible line numbers and the range of the line containing the cursor are found.
+        let (line_numbers, current_line_y) = {
+            let unique = match ui.widget_graph()
+                .widget(state.ids.text_edit)
+                .and_then(|container| container.state_and_style::<text_edit::State, text_edit::Style>())
+            {
+                Some(unique) => unique,
+                None => return events,
+            };
+            let line_infos = unique.state.line_infos();
+
+            // Number the first visible line of each newline-separated line of text.
+            let mut line_numbers: Vec<(usize, Range)> = Vec::new();
+            let (first, line_rects) =
+                text::line::rects_below(line_infos, font_size, text_rect, text::Justify::Left,
+                                        Align::End, line_spacing, visible.top());
+            for (info, line_rect) in line_infos[first..].iter().zip(line_rects) {
+                if line_rect.top() < visible.bottom() {
+                    break;
+                }
+                if line_rect.bottom() > visible.top() {
+                    continue;
+                }
+                if let Ok(line_idx) = state.line_starts.binary_search(&info.start_byte) {
+                    line_numbers.push((line_idx + 1, line_rect.y));
+                }
+            }
+
+            let cursor_line = match unique.state.cursor() {
+                text_edit::Cursor::Idx(idx) => idx.line,
+                text_edit::Cursor::Selection { end, .. } => end.line,
+            };
+            let current_line_y = if cursor_line < line_infos.len() {
+                let top = text_rect.top() - cursor_line as Scalar * (font_size as Scalar + line_spacing);
+                Some(Range::new(top - font_size as Scalar, top))
+            } else {
+                None
+            };
+
+            (line_numbers, current_line_y)
+        };
+
+        if state.ids.line_numbers.len() < line_numbers.len() {
+            let num_line_numbers = line_numbers.len();
+            let id_gen = &mut ui.widget_id_generator();
+            state.update(|state| state.ids.line_numbers.resize(num_line_numbers, id_gen));
+        }
+
+        let line_number_color = style.line_number_color(ui.theme());
+        let line_number_w = (gutter_w - gutter_padding * 2.0).max(0.0);
+        let line_number_x = text_rect.left() - gutter_w / 2.0;
+        let iter = state.ids.line_numbers.iter().zip(&line_numbers);
+        for (&line_number_id, &(line_number, y)) in iter {
+            widget::Text::new(&line_number.to_string())
+                .no_line_wrap()
+                .right_justify()
+                .font_id(font_id)
+                .font_size(font_size)
+                .color(line_number_color)
+                .w_h(line_number_w, y.len())
+                .x_y(line_number_x, y.middle())
+                .parent(state.ids.canvas)
+                .graphics_for(id)
+                .set(line_number_id, ui);
+        }
+
+        // Highlight the line containing the cursor while the `TextEdit` is being edited.
+        if is_editing {
+            if let Some(y) = current_line_y {
+                let current_line_rect = Rect { x: text_rect.x, y: y };
+                widget::Rectangle::fill(current_line_rect.dim())
+                    .xy(current_line_rect.xy())
+                    .color(style.current_line_color(ui.theme()))
+                    .depth(1.0)
+                    .parent(state.ids.canvas)
+                    .graphics_for(state.ids.text_edit)
+                    .set(state.ids.current_line, ui);
+            }
+        }
+
+        events
+    }
+
+}
+
+impl<'a> Colorable for CodeEdit<'a> {
+    builder_method!(color { style.color = Some(Color) });
+}
+
+/// The byte index at which each newline-separated line of the given text begins.
+fn line_starts(text: &str) -> Vec<usize> {
+    std::iter::once(0)
+        .chain(text.match_indices('\n').map(|(i, _)| i + 1))
+        .collect()
+}
@@ -65,6 +65,9 @@ pub struct Style {
     /// The ID of the font used to display the labels.
     #[conrod(default = "theme.font_id")]
     pub label_font_id: Option<Option<text::font::Id>>,
+    /// How item labels that are wider than their `Button` are displayed.
+    #[conrod(default = "widget::text::Overflow::Ellipsis(text::Ellipsis::End)")]
+    pub label_overflow: Option<widget::text::Overflow>,
 }
 
 widget_ids! {
@@ -190,6 +193,14 @@ impl<'a, T> DropDownList<'a, T> {
         self
     }
 
+    /// Specify how labels that are wider than their `Button`s are displayed.
+    ///
+    /// By default, the end of the label is replaced with "…".
+    pub fn label_overflow(mut self, overflow: widget::text::Overflow) -> Self {
+        self.style.label_overflow = Some(overflow);
+        self
+    }
+
 }
 
 
@@ -362,6 +373,7 @@ impl Style {
             label_x: self.label_x,
             label_y: self.label_y,
             label_font_id: self.label_font_id,
+            label_overflow: self.label_overflow,
         }
     }
 
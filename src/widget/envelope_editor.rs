@@ -1,7 +1,7 @@
 //! The `EnvelopeEditor` widget and related items.
 
 use {Color, Colorable, Borderable, FontSize, Labelable, Positionable, Sizeable, Widget};
-use num::Float;
+use num::{Float, NumCast};
 use position::{Direction, Edge, Point, Rect, Scalar};
 use std;
 use text;
@@ -75,9 +75,19 @@ widget_ids! {
 /// Represents the state of the EnvelopeEditor widget.
 pub struct State {
     pressed_point: Option<usize>,
+    pressed_point_origin: Option<PointOrigin>,
     ids: Ids,
 }
 
+/// Where the pressed point came from, so that its drag may be undone if cancelled.
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum PointOrigin {
+    /// The point was added by the press.
+    Added,
+    /// The point existed at the given position before the press.
+    Existing([Scalar; 2]),
+}
+
 
 /// `EnvPoint` must be implemented for any type that is used as a 2D point within the
 /// EnvelopeEditor.
@@ -236,6 +246,7 @@ impl<'a, E> Widget for EnvelopeEditor<'a, E>
     fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
         State {
             pressed_point: None,
+            pressed_point_origin: None,
             ids: Ids::new(id_gen),
         }
     }
@@ -310,6 +321,7 @@ impl<'a, E> Widget for EnvelopeEditor<'a, E>
 
         // Track the currently pressed point if any.
         let mut pressed_point = state.pressed_point;
+        let mut pressed_point_origin = state.pressed_point_origin;
 
         // Handle all events that have occurred to the EnvelopeEditor since the last update.
         //
@@ -317,6 +329,7 @@ impl<'a, E> Widget for EnvelopeEditor<'a, E>
         // - New points via left `Click`.
         // - Remove points via right `Click`.
         // - Dragging points via left `Drag`.
+        // - Undoing a point's drag via a left `DragCancelled`.
         let mut events = Vec::new();
         'events: for widget_event in ui.widget_input(id).events() {
             use event;
@@ -355,7 +368,9 @@ impl<'a, E> Widget for EnvelopeEditor<'a, E>
 
                             // If the press was over a point, begin dragging it and we're done.
                             if distance <= point_radius.powf(2.0) {
+                                let origin = [NumCast::from(px).unwrap(), NumCast::from(py).unwrap()];
                                 pressed_point = Some(i);
+                                pressed_point_origin = Some(PointOrigin::Existing(origin));
                                 continue 'events;
                             }
 
@@ -383,17 +398,20 @@ impl<'a, E> Widget for EnvelopeEditor<'a, E>
                                 let idx = env.len();
                                 let event = Event::AddPoint { i: idx, point: new_point };
                                 pressed_point = Some(idx);
+                                pressed_point_origin = Some(PointOrigin::Added);
                                 events.push(event);
                             },
                             (None, Some(_)) => {
                                 let idx = 0;
                                 let event = Event::AddPoint { i: idx, point: new_point };
                                 pressed_point = Some(idx);
+                                pressed_point_origin = Some(PointOrigin::Added);
                                 events.push(event);
                             },
                             (Some(_), Some(idx)) => {
                                 let event = Event::AddPoint { i: idx, point: new_point };
                                 pressed_point = Some(idx);
+                                pressed_point_origin = Some(PointOrigin::Added);
                                 events.push(event);
                             },
                         }
@@ -419,7 +437,26 @@ impl<'a, E> Widget for EnvelopeEditor<'a, E>
                 event::Widget::Release(release) => {
                     if let event::Button::Mouse(MouseButton::Left, _) = release.button {
                         pressed_point = None;
+                        pressed_point_origin = None;
+                    }
+                },
+
+                // A cancelled left `Drag` returns the `pressed_point` to where it was pressed, or
+                // removes it if the press added it.
+                event::Widget::DragCancelled(cancelled) if cancelled.button == input::MouseButton::Left => {
+                    if let (Some(idx), Some(origin)) = (pressed_point, pressed_point_origin) {
+                        let event = match origin {
+                            PointOrigin::Added => Event::RemovePoint { i: idx },
+                            PointOrigin::Existing([x, y]) => Event::MovePoint {
+                                i: idx,
+                                x: NumCast::from(x).unwrap(),
+                                y: NumCast::from(y).unwrap(),
+                            },
+                        };
+                        events.push(event);
                     }
+                    pressed_point = None;
+                    pressed_point_origin = None;
                 },
 
                 // A left `Drag` moves the `pressed_point` if there is one.
@@ -444,8 +481,11 @@ impl<'a, E> Widget for EnvelopeEditor<'a, E>
             }
         }
 
-        if state.pressed_point != pressed_point {
-            state.update(|state| state.pressed_point = pressed_point);
+        if state.pressed_point != pressed_point || state.pressed_point_origin != pressed_point_origin {
+            state.update(|state| {
+                state.pressed_point = pressed_point;
+                state.pressed_point_origin = pressed_point_origin;
+            });
         }
 
         // Ensure that the local version of the `env` is up to date for drawing.
@@ -100,6 +100,10 @@ pub struct Events<M, D, S>
 #[derive(Clone, Debug)]
 pub enum Event<Selection, Direction, Size> {
     /// The next `Item` is ready for instantiation.
+    ///
+    /// The item's widget is sized to fit the item, so labelled items should be instantiated as
+    /// `Button`s (or `Text` with an `ellipsis`) so that labels wider than the item are shortened
+    /// with "…" rather than drawn beyond its bounds.
     Item(widget::list::Item<Direction, Size>),
     /// A change in selection has occurred.
     Selection(Selection),
@@ -27,6 +27,7 @@ pub use self::primitive::text::{self, Text};
 pub use self::bordered_rectangle::BorderedRectangle;
 pub use self::button::Button;
 pub use self::canvas::Canvas;
+pub use self::code_edit::CodeEdit;
 pub use self::collapsible_area::CollapsibleArea;
 pub use self::drop_down_list::DropDownList;
 pub use self::envelope_editor::EnvelopeEditor;
@@ -38,9 +39,11 @@ pub use self::matrix::Matrix;
 pub use self::graph::Graph;
 pub use self::number_dialer::NumberDialer;
 pub use self::plot_path::PlotPath;
+pub use self::popup_menu::PopupMenu;
 pub use self::range_slider::RangeSlider;
 pub use self::rounded_rectangle::RoundedRectangle;
 pub use self::scrollbar::Scrollbar;
+pub use self::selectable_text::SelectableText;
 pub use self::slider::Slider;
 pub use self::tabs::Tabs;
 pub use self::text_box::TextBox;
@@ -65,6 +68,7 @@ pub mod primitive;
 pub mod bordered_rectangle;
 pub mod button;
 pub mod canvas;
+pub mod code_edit;
 pub mod collapsible_area;
 pub mod drop_down_list;
 pub mod envelope_editor;
@@ -76,9 +80,11 @@ pub mod matrix;
 pub mod graph;
 pub mod number_dialer;
 pub mod plot_path;
+pub mod popup_menu;
 pub mod range_slider;
 pub mod rounded_rectangle;
 pub mod scrollbar;
+pub mod selectable_text;
 pub mod slider;
 pub mod tabs;
 pub mod text_box;
@@ -236,6 +242,44 @@ pub struct CommonBuilder {
     /// default.
     /// - Any **Graphic** child of *b* will be considered as a **Graphic** child of *a*.
     pub maybe_graphics_for: Option<Id>,
+    /// Whether or not the **Widget** may be focused via `Tab` and `Shift+Tab`.
+    ///
+    /// If `None`, the default given by **Widget::is_focusable** is used.
+    pub maybe_focusable: Option<bool>,
+    /// The position of the **Widget** within the tab order, if one was given.
+    pub maybe_tab_index: Option<u32>,
+    /// The text displayed as the tooltip once the mouse has rested over the **Widget**, if any.
+    pub maybe_tooltip_text: Option<TooltipText>,
+    /// The widget displayed as the tooltip once the mouse has rested over the **Widget**, if any.
+    pub maybe_tooltip_widget: Option<Id>,
+}
+
+/// Content displayed beside a **Widget** once the mouse has rested over it for the
+/// `theme.tooltip_delay`.
+///
+/// The tooltip is hidden as soon as the mouse moves or a mouse button is pressed.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Tooltip {
+    /// Text displayed within a box beneath the mouse, styled by the **Theme**.
+    Text(String),
+    /// Some other widget, instantiated by the user while `Ui::tooltip` yields this tooltip.
+    ///
+    /// The widget should be floating and may be positioned via `Ui::tooltip_rect`.
+    Widget(Id),
+}
+
+/// The text of a `Tooltip`, given to a widget via the `tooltip` builder method.
+///
+/// A `&'static str` converts directly, while text that is formatted or localised at runtime is
+/// stored for the current `set_widgets` stage via `UiCell::tooltip_text`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum TooltipText {
+    /// Text that is known at compile time.
+    Static(&'static str),
+    /// The text at the given index of that stored for the current `set_widgets` stage.
+    ///
+    /// Produced by `UiCell::tooltip_text`.
+    Stage(usize),
 }
 
 /// Styling and positioning data that is common between all widget types.
@@ -698,6 +742,14 @@ pub trait Widget: Common + Sized {
         is_over_rect
     }
 
+    /// Whether or not the widget may be focused via `Tab` and `Shift+Tab` by default.
+    ///
+    /// Widgets that respond to the keyboard (e.g. `Button`, `Toggle`, `Slider` and `TextEdit`)
+    /// return `true`. This may be overridden for a single instance via **Widget::focusable**.
+    fn is_focusable(&self) -> bool {
+        false
+    }
+
 
     // None of the following methods should require overriding. Perhaps they should be split off
     // into a separate trait which is impl'ed for W: Widget to make this clearer?
@@ -766,6 +818,43 @@ pub trait Widget: Common + Sized {
         self
     }
 
+    /// Set whether or not the widget may be focused via `Tab` and `Shift+Tab`.
+    ///
+    /// By default, this is determined by **Widget::is_focusable**.
+    fn focusable(mut self, focusable: bool) -> Self {
+        self.common_mut().maybe_focusable = Some(focusable);
+        self
+    }
+
+    /// Set the position of the widget within the tab order.
+    ///
+    /// Widgets with a `tab_index` are focused in ascending order before all other focusable
+    /// widgets, which are focused in the order in which they are set.
+    fn tab_index(mut self, tab_index: u32) -> Self {
+        self.common_mut().maybe_tab_index = Some(tab_index);
+        self
+    }
+
+    /// Display the given text once the mouse has rested over the widget.
+    ///
+    /// Text formatted at runtime may be given via `UiCell::tooltip_text`.
+    fn tooltip<T>(mut self, text: T) -> Self
+        where T: Into<TooltipText>,
+    {
+        self.common_mut().maybe_tooltip_text = Some(text.into());
+        self.common_mut().maybe_tooltip_widget = None;
+        self
+    }
+
+    /// Display the widget with the given `Id` once the mouse has rested over the widget.
+    ///
+    /// The tooltip widget must be instantiated by the user while `Ui::tooltip` yields it.
+    fn tooltip_widget(mut self, id: Id) -> Self {
+        self.common_mut().maybe_tooltip_widget = Some(id);
+        self.common_mut().maybe_tooltip_text = None;
+        self
+    }
+
     /// Indicates that all widgets who are children of this widget should be cropped to the
     /// `kid_area` of this widget.
     fn crop_kids(mut self) -> Self {
@@ -1099,6 +1188,21 @@ fn set_widget<'a, 'b, W>(widget: W, id: Id, ui: &'a mut UiCell<'b>) -> W::Event
         });
     }
 
+    // Add the widget to the tab order if it may be focused via the keyboard.
+    if widget.common().maybe_focusable.unwrap_or_else(|| widget.is_focusable()) {
+        let ui: &mut Ui = ui::ref_mut_from_ui_cell(ui);
+        ui::push_focusable_widget(ui, id, widget.common().maybe_tab_index);
+    }
+
+    // Register the widget's tooltip so that it may be displayed while the mouse rests over it.
+    if let Some(tooltip_widget) = widget.common().maybe_tooltip_widget {
+        let ui: &mut Ui = ui::ref_mut_from_ui_cell(ui);
+        ui::push_tooltip(ui, id, Tooltip::Widget(tooltip_widget));
+    } else if let Some(text) = widget.common().maybe_tooltip_text {
+        let ui: &mut Ui = ui::ref_mut_from_ui_cell(ui);
+        ui::push_tooltip_text(ui, id, text);
+    }
+
     // Unwrap the widget's previous common state. If there is no previous common state, we'll
     // use the new state in it's place.
     let prev_common = maybe_prev_common.unwrap_or_else(|| CommonState {
@@ -1204,6 +1308,12 @@ impl<'a, T> std::ops::Deref for State<'a, T> {
     }
 }
 
+impl From<&'static str> for TooltipText {
+    fn from(text: &'static str) -> Self {
+        TooltipText::Static(text)
+    }
+}
+
 impl Default for CommonBuilder {
     fn default() -> Self {
         CommonBuilder {
@@ -1215,6 +1325,10 @@ impl Default for CommonBuilder {
             maybe_x_scroll: None,
             maybe_y_scroll: None,
             crop_kids: false,
+            maybe_focusable: None,
+            maybe_tab_index: None,
+            maybe_tooltip_text: None,
+            maybe_tooltip_widget: None,
         }
     }
 }
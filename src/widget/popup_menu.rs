@@ -0,0 +1,651 @@
+//! A menu of items that pops up over other widgets, e.g. upon a right-click.
+
+use {Color, Colorable, FontSize, Borderable, Positionable, Sizeable, Ui};
+use event;
+use input;
+use position::{Point, Rect, Scalar};
+use text;
+use utils;
+use widget::{self, Widget};
+
+
+/// A menu of selectable items that floats above all other widgets.
+///
+/// The top left corner of the menu is placed at the position of the **PopupMenu** widget itself,
+/// e.g. `.xy(ui.global_input().current.mouse.xy)`, while the menu is kept within the bounds of
+/// the window. Items may open nested submenus to either side.
+///
+/// Items are highlighted by hovering the mouse over them or via the `Up` and `Down` keys. `Right`
+/// and `Left` enter and leave submenus, `Return` and `Space` select the highlighted item and
+/// `Escape` closes the menu.
+///
+/// The menu is displayed for as long as it is set. The user should stop setting it upon receiving
+/// an `Event`, which is produced when an item is selected or when the user presses the mouse
+/// outside of the menu.
+#[derive(WidgetCommon_)]
+pub struct PopupMenu<'a> {
+    #[conrod(common_builder)]
+    common: widget::CommonBuilder,
+    items: &'a [Item<'a>],
+    style: Style,
+}
+
+/// Unique styling for the PopupMenu.
+#[derive(Copy, Clone, Debug, Default, PartialEq, WidgetStyle_)]
+pub struct Style {
+    /// The color of the menu's background.
+    #[conrod(default = "theme.shape_color")]
+    pub color: Option<Color>,
+    /// The width of the border around each menu.
+    #[conrod(default = "theme.border_width")]
+    pub border: Option<Scalar>,
+    /// The color of the border around each menu and of the separators.
+    #[conrod(default = "theme.border_color")]
+    pub border_color: Option<Color>,
+    /// The color of the item labels and check marks.
+    #[conrod(default = "theme.label_color")]
+    pub label_color: Option<Color>,
+    /// The font size of the item labels and shortcuts.
+    #[conrod(default = "theme.font_size_small")]
+    pub label_font_size: Option<FontSize>,
+    /// The ID of the font used to display the item labels and shortcuts.
+    #[conrod(default = "theme.font_id")]
+    pub label_font_id: Option<Option<text::font::Id>>,
+    /// The color of the labels of disabled items.
+    #[conrod(default = "theme.label_color.alpha(0.4)")]
+    pub disabled_color: Option<Color>,
+    /// The color of the keyboard shortcuts shown to the right of the labels.
+    #[conrod(default = "theme.label_color.alpha(0.6)")]
+    pub shortcut_color: Option<Color>,
+    /// The color of the highlight behind the highlighted item.
+    #[conrod(default = "theme.label_color.alpha(0.15)")]
+    pub highlight_color: Option<Color>,
+    /// The height of each selectable item.
+    #[conrod(default = "24.0")]
+    pub item_height: Option<Scalar>,
+    /// The height of each separator.
+    #[conrod(default = "9.0")]
+    pub separator_height: Option<Scalar>,
+}
+
+/// An item within a **PopupMenu**.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Item<'a> {
+    /// An item that may be selected.
+    Entry {
+        /// The text displayed for the item.
+        label: &'a str,
+        /// A keyboard shortcut displayed to the right of the label.
+        shortcut: Option<&'a str>,
+        /// Whether or not a check mark is displayed beside the label.
+        checked: bool,
+        /// Whether or not the item may be highlighted and selected.
+        enabled: bool,
+    },
+    /// An item that opens a nested menu of items when highlighted.
+    Submenu {
+        /// The text displayed for the item.
+        label: &'a str,
+        /// The items within the nested menu.
+        items: &'a [Item<'a>],
+        /// Whether or not the item may be highlighted and opened.
+        enabled: bool,
+    },
+    /// A line separating groups of items.
+    Separator,
+}
+
+/// The events produced by the **PopupMenu**, upon which the menu should be closed.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Event {
+    /// The item at the given path was selected.
+    ///
+    /// The path holds the index of the item within each nested menu, starting with the index
+    /// within the top-level items.
+    Select(Vec<usize>),
+    /// The menu was dismissed without selecting an item.
+    Close,
+}
+
+widget_ids! {
+    struct Ids {
+        panels[],
+        highlights[],
+        separators[],
+        checks[],
+        labels[],
+        shortcuts[],
+        arrows[],
+    }
+}
+
+/// The state of the PopupMenu.
+pub struct State {
+    ids: Ids,
+    /// The index of the highlighted item within each open menu, starting with the top-level menu.
+    ///
+    /// Each highlighted enabled `Submenu` opens the menu following it.
+    path: Vec<usize>,
+}
+
+/// The layout of a single open menu.
+struct Level<'a> {
+    items: &'a [Item<'a>],
+    rect: Rect,
+    item_rects: Vec<Rect>,
+    label_w: Scalar,
+    shortcut_w: Scalar,
+}
+
+impl<'a> Item<'a> {
+
+    /// A selectable item with the given label.
+    pub fn entry(label: &'a str) -> Self {
+        Item::Entry { label: label, shortcut: None, checked: false, enabled: true }
+    }
+
+    /// An item with the given label that opens a nested menu of the given items.
+    pub fn submenu(label: &'a str, items: &'a [Item<'a>]) -> Self {
+        Item::Submenu { label: label, items: items, enabled: true }
+    }
+
+    /// Display the given keyboard shortcut to the right of an `Entry`'s label.
+    pub fn shortcut(self, shortcut: &'a str) -> Self {
+        match self {
+            Item::Entry { label, checked, enabled, .. } =>
+                Item::Entry { label: label, shortcut: Some(shortcut), checked: checked, enabled: enabled },
+            item => item,
+        }
+    }
+
+    /// Whether or not a check mark is displayed beside an `Entry`'s label.
+    pub fn checked(self, checked: bool) -> Self {
+        match self {
+            Item::Entry { label, shortcut, enabled, .. } =>
+                Item::Entry { label: label, shortcut: shortcut, checked: checked, enabled: enabled },
+            item => item,
+        }
+    }
+
+    /// Whether or not the item may be highlighted and selected.
+    pub fn enabled(self, enabled: bool) -> Self {
+        match self {
+            Item::Entry { label, shortcut, checked, .. } =>
+                Item::Entry { label: label, shortcut: shortcut, checked: checked, enabled: enabled },
+            Item::Submenu { label, items, .. } =>
+                Item::Submenu { label: label, items: items, enabled: enabled },
+            Item::Separator => Item::Separator,
+        }
+    }
+
+    /// Whether or not the item may be highlighted.
+    fn is_enabled(&self) -> bool {
+        match *self {
+            Item::Entry { enabled, .. } | Item::Submenu { enabled, .. } => enabled,
+            Item::Separator => false,
+        }
+    }
+
+    /// The nested items opened by the item, if it is an enabled `Submenu`.
+    fn submenu_items(&self) -> Option<&'a [Item<'a>]> {
+        match *self {
+            Item::Submenu { items, enabled: true, .. } => Some(items),
+            _ => None,
+        }
+    }
+
+}
+
+impl<'a> PopupMenu<'a> {
+
+    /// Construct a PopupMenu of the given items.
+    pub fn new(items: &'a [Item<'a>]) -> Self {
+        PopupMenu {
+            common: widget::CommonBuilder::default(),
+            items: items,
+            style: Style::default(),
+        }
+    }
+
+    /// Specify the font used for displaying the item labels and shortcuts.
+    pub fn label_font_id(mut self, font_id: text::font::Id) -> Self {
+        self.style.label_font_id = Some(Some(font_id));
+        self
+    }
+
+    builder_methods!{
+        pub label_color { style.label_color = Some(Color) }
+        pub label_font_size { style.label_font_size = Some(FontSize) }
+        pub disabled_color { style.disabled_color = Some(Color) }
+        pub shortcut_color { style.shortcut_color = Some(Color) }
+        pub highlight_color { style.highlight_color = Some(Color) }
+        pub item_height { style.item_height = Some(Scalar) }
+        pub separator_height { style.separator_height = Some(Scalar) }
+    }
+
+}
+
+impl<'a> Widget for PopupMenu<'a> {
+    type State = State;
+    type Style = Style;
+    type Event = Option<Event>;
+
+    fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
+        State {
+            ids: Ids::new(id_gen),
+            path: Vec::new(),
+        }
+    }
+
+    fn style(&self) -> Self::Style {
+        self.style.clone()
+    }
+
+    /// Update the state of the PopupMenu.
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        let widget::UpdateArgs { id, state, rect, style, ui, .. } = args;
+        let PopupMenu { items, .. } = self;
+
+        // If we've no font to use for text logic, bail out without updating.
+        let font_id = match style.label_font_id(&ui.theme)
+            .or(ui.fonts.ids().next())
+            .and_then(|id| ui.fonts.get(id).map(|_| id))
+        {
+            Some(font_id) => font_id,
+            None => return None,
+        };
+
+        let font_size = style.label_font_size(ui.theme());
+        let border = style.border(ui.theme());
+        let item_h = style.item_height(ui.theme());
+        let separator_h = style.separator_height(ui.theme());
+        let window = Rect::from_xy_dim([0.0, 0.0], ui.window_dim());
+        let layout = |path: &[usize]| {
+            let font = ui.fonts.get(font_id).unwrap();
+            let measure = |text: &str| text::line::width(text, font, font_size);
+            layout(items, path, rect.top_left(), window, border, item_h, separator_h, &measure)
+        };
+
+        // A menu that was not open during the previous update opens with no highlighted item, and
+        // ignores the input that opened it.
+        let is_opening = !ui.prev_updated_widgets().contains(&id);
+        let mut path = if is_opening { Vec::new() } else { state.path.clone() };
+
+        // The items may have changed since the previous update, so discard any part of the path
+        // that no longer leads to an enabled item.
+        truncate_path(items, &mut path);
+        let mut event = None;
+
+        if !is_opening {
+            let levels = layout(&path);
+
+            // The item under the given absolute position, if any.
+            let item_at = |xy: Point| {
+                levels.iter().enumerate().rev().filter_map(|(level, l)| {
+                    l.item_rects.iter().position(|r| r.is_over(xy)).map(|i| (level, i))
+                }).next()
+            };
+
+            // Highlight the item under the mouse whenever the mouse moves.
+            let input = &ui.global_input().current;
+            if input.mouse.xy != ui.global_input().start.mouse.xy {
+                if let Some((level, i)) = item_at(input.mouse.xy) {
+                    let item = levels[level].items[i];
+                    if let Item::Separator = item {
+                    } else {
+                        path.truncate(level);
+                        if item.is_enabled() {
+                            path.push(i);
+                        }
+                    }
+                }
+            }
+
+            for ui_event in ui.global_input().events().ui() {
+                match *ui_event {
+
+                    // Select the item under the mouse on release, or close the menu if the mouse
+                    // was pressed outside of it.
+                    event::Ui::Press(_, event::Press { button: event::Button::Mouse(_, xy), .. }) => {
+                        if !levels.iter().any(|l| l.rect.is_over(xy)) {
+                            event = Some(Event::Close);
+                        }
+                    },
+                    event::Ui::Release(_, event::Release { button: event::Button::Mouse(input::MouseButton::Left, xy), .. }) => {
+                        if let Some((level, i)) = item_at(xy) {
+                            if let Item::Entry { enabled: true, .. } = levels[level].items[i] {
+                                let mut selected = path[..level.min(path.len())].to_vec();
+                                selected.push(i);
+                                event = Some(Event::Select(selected));
+                            }
+                        }
+                    },
+
+                    // Navigate the menu via the keyboard.
+                    event::Ui::Press(_, event::Press { button: event::Button::Keyboard(key), .. }) => {
+                        let level = path.len().saturating_sub(1);
+                        let level_items = open_items(items, &path[..level]);
+                        let highlighted = path.last().and_then(|&i| level_items.get(i).cloned());
+                        match key {
+                            input::Key::Down | input::Key::Up => {
+                                let forwards = key == input::Key::Down;
+                                let current = path.get(level).cloned();
+                                if let Some(i) = next_enabled(level_items, current, forwards) {
+                                    path.truncate(level);
+                                    path.push(i);
+                                }
+                            },
+                            input::Key::Right => {
+                                if let Some(submenu) = highlighted.and_then(|item| item.submenu_items()) {
+                                    if let Some(i) = next_enabled(submenu, None, true) {
+                                        path.push(i);
+                                    }
+                                }
+                            },
+                            input::Key::Left => {
+                                if path.len() > 1 {
+                                    path.pop();
+                                }
+                            },
+                            input::Key::Return | input::Key::NumPadEnter | input::Key::Space => {
+                                match highlighted {
+                                    Some(Item::Entry { enabled: true, .. }) =>
+                                        event = Some(Event::Select(path.clone())),
+                                    Some(Item::Submenu { items: submenu, enabled: true, .. }) => {
+                                        if let Some(i) = next_enabled(submenu, None, true) {
+                                            path.push(i);
+                                        }
+                                    },
+                                    _ => (),
+                                }
+                            },
+                            input::Key::Escape => {
+                                if path.len() > 1 {
+                                    path.pop();
+                                } else {
+                                    event = Some(Event::Close);
+                                }
+                            },
+                            _ => (),
+                        }
+                    },
+
+                    _ => (),
+                }
+            }
+        }
+
+        if state.path != path {
+            state.update(|state| state.path = path.clone());
+        }
+
+        // Ensure there are enough widget ids for every open menu and item.
+        let levels = layout(&path);
+        let num_items = levels.iter().map(|l| l.items.len()).sum();
+        if state.ids.panels.len() < levels.len() || state.ids.labels.len() < num_items {
+            let num_levels = levels.len();
+            let id_gen = &mut ui.widget_id_generator();
+            state.update(|state| {
+                state.ids.panels.resize(num_levels, id_gen);
+                state.ids.highlights.resize(num_levels, id_gen);
+                state.ids.separators.resize(num_items, id_gen);
+                state.ids.checks.resize(num_items, id_gen);
+                state.ids.labels.resize(num_items, id_gen);
+                state.ids.shortcuts.resize(num_items, id_gen);
+                state.ids.arrows.resize(num_items, id_gen);
+            });
+        }
+
+        let color = style.color(ui.theme());
+        let border_color = style.border_color(ui.theme());
+        let label_color = style.label_color(ui.theme());
+        let disabled_color = style.disabled_color(ui.theme());
+        let shortcut_color = style.shortcut_color(ui.theme());
+        let highlight_color = style.highlight_color(ui.theme());
+        let text_h = font_size as Scalar;
+
+        let mut n = 0;
+        for (level, l) in levels.iter().enumerate() {
+            let panel = state.ids.panels[level];
+            widget::BorderedRectangle::new(l.rect.dim())
+                .xy(l.rect.xy())
+                .color(color)
+                .border(border)
+                .border_color(border_color)
+                .parent(id)
+                .floating(true)
+                .set(panel, ui);
+
+            if let Some(&i) = path.get(level) {
+                let highlight = l.item_rects[i];
+                widget::Rectangle::fill(highlight.dim())
+                    .xy(highlight.xy())
+                    .color(highlight_color)
+                    .parent(panel)
+                    .graphics_for(panel)
+                    .set(state.ids.highlights[level], ui);
+            }
+
+            for (item, &item_rect) in l.items.iter().zip(&l.item_rects) {
+                let (x, y) = (item_rect.left(), item_rect.y());
+                let (label, enabled) = match *item {
+                    Item::Entry { label, enabled, .. } | Item::Submenu { label, enabled, .. } =>
+                        (label, enabled),
+                    Item::Separator => {
+                        let start = [item_rect.left() + item_h / 2.0, y];
+                        let end = [item_rect.right() - item_h / 2.0, y];
+                        widget::Line::abs(start, end)
+                            .color(border_color)
+                            .parent(panel)
+                            .graphics_for(panel)
+                            .set(state.ids.separators[n], ui);
+                        n += 1;
+                        continue;
+                    },
+                };
+                let text_color = if enabled { label_color } else { disabled_color };
+
+                widget::Text::new(label)
+                    .font_id(font_id)
+                    .font_size(font_size)
+                    .color(text_color)
+                    .no_line_wrap()
+                    .left_justify()
+                    .w_h(l.label_w, text_h)
+                    .x_y(x + item_h + l.label_w / 2.0, y)
+                    .parent(panel)
+                    .graphics_for(panel)
+                    .set(state.ids.labels[n], ui);
+
+                match *item {
+                    Item::Entry { checked, shortcut, .. } => {
+                        if checked {
+                            let (cx, s) = (x + item_h / 2.0, item_h * 0.2);
+                            let points = [[cx - s, y], [cx - s * 0.3, y - s * 0.7], [cx + s, y + s * 0.8]];
+                            widget::PointPath::abs(points.iter().cloned())
+                                .color(text_color)
+                                .thickness(2.0)
+                                .parent(panel)
+                                .graphics_for(panel)
+                                .set(state.ids.checks[n], ui);
+                        }
+                        if let Some(shortcut) = shortcut {
+                            let right = item_rect.right() - item_h;
+                            widget::Text::new(shortcut)
+                                .font_id(font_id)
+                                .font_size(font_size)
+                                .color(if enabled { shortcut_color } else { disabled_color })
+                                .no_line_wrap()
+                                .right_justify()
+                                .w_h(l.shortcut_w, text_h)
+                                .x_y(right - l.shortcut_w / 2.0, y)
+                                .parent(panel)
+                                .graphics_for(panel)
+                                .set(state.ids.shortcuts[n], ui);
+                        }
+                    },
+                    Item::Submenu { .. } => {
+                        let (cx, s) = (item_rect.right() - item_h / 2.0, item_h * 0.15);
+                        let points = [[cx - s, y + s * 1.5], [cx - s, y - s * 1.5], [cx + s, y]];
+                        widget::Polygon::abs_fill(points.iter().cloned())
+                            .color(text_color)
+                            .parent(panel)
+                            .graphics_for(panel)
+                            .set(state.ids.arrows[n], ui);
+                    },
+                    Item::Separator => (),
+                }
+                n += 1;
+            }
+        }
+
+        event
+    }
+
+}
+
+/// The position at which a **PopupMenu** should be opened if the given widget was right-clicked
+/// since the last update.
+///
+/// This is the position of the mouse at the time of the most recent right-click.
+pub fn right_click_xy(ui: &Ui, widget: widget::Id) -> Option<Point> {
+    let widget_xy = match ui.xy_of(widget) {
+        Some(xy) => xy,
+        None => return None,
+    };
+    ui.widget_input(widget).clicks().right().last()
+        .map(|click| utils::vec2_add(click.xy, widget_xy))
+}
+
+/// Truncate the path at the first index that does not refer to an enabled item within its menu.
+fn truncate_path(items: &[Item], path: &mut Vec<usize>) {
+    let mut level_items = Some(items);
+    let mut len = 0;
+    for &i in path.iter() {
+        match level_items.and_then(|items| items.get(i)) {
+            Some(item) if item.is_enabled() => {
+                level_items = item.submenu_items();
+                len += 1;
+            },
+            _ => break,
+        }
+    }
+    path.truncate(len);
+}
+
+/// The items of the menu opened by following the given path of submenus from the top level.
+fn open_items<'a>(items: &'a [Item<'a>], path: &[usize]) -> &'a [Item<'a>] {
+    path.iter().fold(items, |items, &i| {
+        items.get(i).and_then(|item| item.submenu_items()).unwrap_or(items)
+    })
+}
+
+/// The index of the next enabled item after `current` (or before it if not `forwards`), wrapping
+/// around at either end.
+fn next_enabled(items: &[Item], current: Option<usize>, forwards: bool) -> Option<usize> {
+    let len = items.len();
+    (1..len + 1)
+        .map(|step| match current {
+            Some(i) if forwards => (i + step) % len,
+            Some(i) => (i + len - step % len) % len,
+            None if forwards => step - 1,
+            None => len - step,
+        })
+        .find(|&i| items[i].is_enabled())
+}
+
+/// Lay out the top-level menu with its top left corner at `top_left` along with each submenu
+/// opened by the `path`, keeping each within the `window`.
+fn layout<'a>(items: &'a [Item<'a>],
+              path: &[usize],
+              top_left: Point,
+              window: Rect,
+              border: Scalar,
+              item_h: Scalar,
+              separator_h: Scalar,
+              measure: &Fn(&str) -> Scalar) -> Vec<Level<'a>>
+{
+    let mut levels: Vec<Level> = Vec::new();
+    let mut level_items = Some(items);
+    while let Some(items) = level_items.take() {
+        let label_w = items.iter()
+            .map(|item| match *item {
+                Item::Entry { label, .. } | Item::Submenu { label, .. } => measure(label),
+                Item::Separator => 0.0,
+            })
+            .fold(0.0, Scalar::max);
+        let shortcut_w = items.iter()
+            .map(|item| match *item {
+                Item::Entry { shortcut: Some(shortcut), .. } => measure(shortcut),
+                _ => 0.0,
+            })
+            .fold(0.0, Scalar::max);
+
+        // Leave a column for check marks to the left of the labels and another for submenu arrows
+        // to the right of the shortcuts.
+        let shortcut_column = if shortcut_w > 0.0 { item_h + shortcut_w } else { 0.0 };
+        let w = item_h + label_w + shortcut_column + item_h + border * 2.0;
+        let h = items.iter()
+            .map(|item| match *item {
+                Item::Separator => separator_h,
+                _ => item_h,
+            })
+            .fold(border * 2.0, |total, h| total + h);
+
+        // Submenus open beside their item, flipping to the other side of their parent if there is
+        // no room within the window.
+        let (left, top) = match levels.last() {
+            None => {
+                let left = if top_left[0] + w > window.right() { window.right() - w } else { top_left[0] };
+                (left, top_left[1])
+            },
+            Some(parent) => {
+                let item_rect = parent.item_rects[path[levels.len() - 1]];
+                let right = parent.rect.right();
+                let left = if right + w > window.right() { parent.rect.left() - w } else { right };
+                (left, item_rect.top() + border)
+            },
+        };
+        let left = left.max(window.left());
+        let top = if top - h < window.bottom() { window.bottom() + h } else { top };
+        let top = top.min(window.top());
+        let rect = Rect::from_corners([left, top], [left + w, top - h]);
+
+        let mut item_rects = Vec::with_capacity(items.len());
+        let mut item_top = rect.top() - border;
+        for item in items {
+            let item_h = match *item {
+                Item::Separator => separator_h,
+                _ => item_h,
+            };
+            let item_rect = Rect::from_corners([rect.left() + border, item_top],
+                                               [rect.right() - border, item_top - item_h]);
+            item_rects.push(item_rect);
+            item_top -= item_h;
+        }
+
+        // Open the submenu of the highlighted item, if any.
+        level_items = path.get(levels.len()).and_then(|&i| items.get(i)).and_then(|item| item.submenu_items());
+
+        levels.push(Level {
+            items: items,
+            rect: rect,
+            item_rects: item_rects,
+            label_w: label_w,
+            shortcut_w: shortcut_w,
+        });
+    }
+    levels
+}
+
+
+impl<'a> Colorable for PopupMenu<'a> {
+    builder_method!(color { style.color = Some(Color) });
+}
+
+impl<'a> Borderable for PopupMenu<'a> {
+    builder_methods!{
+        border { style.border = Some(Scalar) }
+        border_color { style.border_color = Some(Color) }
+    }
+}
@@ -1,7 +1,7 @@
 //! The primitive widget used for displaying text.
 
-use {Color, Colorable, FontSize, Ui, Widget};
-use position::{Dimension, Scalar};
+use {Color, Colorable, FontSize, Theme, Ui, Widget};
+use position::{Dimension, Range, Rect, Scalar};
 use std;
 use text;
 use utils;
@@ -46,13 +46,28 @@ pub struct Style {
     /// The id of the font to use for rendering and layout.
     #[conrod(default = "theme.font_id")]
     pub font_id: Option<Option<text::font::Id>>,
-    // /// The line styling for the text.
-    // #[conrod(default = "None")]
-    // pub line: Option<Option<Line>>,
+    /// How lines that do not fit within the width should be handled when not wrapping.
+    #[conrod(default = "Overflow::Visible")]
+    pub overflow: Option<Overflow>,
+    /// A line drawn below the baseline of each line of text.
+    #[conrod(default = "None")]
+    pub underline: Option<Option<Decoration>>,
+    /// A line drawn through the middle of each line of text.
+    #[conrod(default = "None")]
+    pub strikethrough: Option<Option<Decoration>>,
+    /// A line drawn above each line of text.
+    #[conrod(default = "None")]
+    pub overline: Option<Option<Decoration>>,
+    /// A color filled behind the glyphs of each line of text.
+    #[conrod(default = "None")]
+    pub background_color: Option<Option<Color>>,
+    /// The positions to which tab characters advance.
+    #[conrod(default = "text::TabStops::default()")]
+    pub tab_stops: Option<text::TabStops>,
 }
 
 /// The way in which text should wrap around the width.
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub enum Wrap {
     /// Wrap at the first character that exceeds the width.
     Character,
@@ -60,23 +75,41 @@ pub enum Wrap {
     Whitespace,
 }
 
-// /// Line styling for the **Text**.
-// pub enum Line {
-//     /// Underline the text.
-//     Under,
-//     /// Overline the text.
-//     Over,
-//     /// Strikethrough the text.
-//     Through,
-// }
+/// The way in which lines that exceed the width should be displayed.
+///
+/// This only applies to **Text** that does not wrap, as wrapped lines always fit the width.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Overflow {
+    /// Draw the whole line, even where it exceeds the width.
+    Visible,
+    /// Cut the line at a character boundary and replace the removed text with "…".
+    Ellipsis(text::Ellipsis),
+}
+
+/// The styling for a line decorating the **Text**, i.e. an underline, strikethrough or overline.
+///
+/// The line spans the width of each line of text.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Decoration {
+    /// The thickness of the line.
+    ///
+    /// If `None`, the thickness is derived from the font size.
+    pub thickness: Option<Scalar>,
+    /// The color of the line.
+    ///
+    /// If `None`, the color of the **Text** is used.
+    pub color: Option<Color>,
+}
 
 /// The state to be stored between updates for the **Text**.
 #[derive(Clone, Debug, PartialEq)]
 pub struct State {
     /// An owned version of the string.
+    ///
+    /// If the text was shortened due to an `Overflow::Ellipsis`, this is the shortened string.
     pub string: String,
     /// The indices and width for each line of text within the `string`.
-    pub line_infos: Vec<text::line::Info>,
+    pub line_infos: std::sync::Arc<[text::line::Info]>,
 }
 
 
@@ -109,6 +142,39 @@ impl<'a> Text<'a> {
         self
     }
 
+    /// Shorten lines that exceed the width, replacing the removed characters with "…".
+    ///
+    /// This implies `no_line_wrap`.
+    pub fn ellipsis(mut self, ellipsis: text::Ellipsis) -> Self {
+        self.style.maybe_wrap = Some(None);
+        self.style.overflow = Some(Overflow::Ellipsis(ellipsis));
+        self
+    }
+
+    /// Draw a line below the baseline of each line of text.
+    pub fn underline(mut self) -> Self {
+        self.style.underline = Some(Some(Decoration::default()));
+        self
+    }
+
+    /// Draw a line through the middle of each line of text.
+    pub fn strikethrough(mut self) -> Self {
+        self.style.strikethrough = Some(Some(Decoration::default()));
+        self
+    }
+
+    /// Draw a line above each line of text.
+    pub fn overline(mut self) -> Self {
+        self.style.overline = Some(Some(Decoration::default()));
+        self
+    }
+
+    /// Fill the area behind the glyphs of each line of text with the given color.
+    pub fn background_color(mut self, color: Color) -> Self {
+        self.style.background_color = Some(Some(color));
+        self
+    }
+
     /// A method for specifying the `Font` used for displaying the `Text`.
     pub fn font_id(mut self, font_id: text::font::Id) -> Self {
         self.style.font_id =  Some(Some(font_id));
@@ -140,6 +206,11 @@ impl<'a> Text<'a> {
         pub font_size { style.font_size = Some(FontSize) }
         pub justify { style.justify = Some(text::Justify) }
         pub line_spacing { style.line_spacing = Some(Scalar) }
+        pub overflow { style.overflow = Some(Overflow) }
+        pub underline_style { style.underline = Some(Option<Decoration>) }
+        pub strikethrough_style { style.strikethrough = Some(Option<Decoration>) }
+        pub overline_style { style.overline = Some(Option<Decoration>) }
+        pub tab_stops { style.tab_stops = Some(text::TabStops) }
     }
 
 }
@@ -153,7 +224,7 @@ impl<'a> Widget for Text<'a> {
     fn init_state(&self, _: widget::id::Generator) -> Self::State {
         State {
             string: String::new(),
-            line_infos: Vec::new(),
+            line_infos: Vec::new().into(),
         }
     }
 
@@ -175,9 +246,10 @@ impl<'a> Widget for Text<'a> {
         };
 
         let font_size = self.style.font_size(&ui.theme);
+        let tab_stops = self.style.tab_stops(&ui.theme);
         let mut max_width = 0.0;
         for line in self.text.lines() {
-            let width = text::line::width(line, font, font_size);
+            let width = text::line::width_with_tab_stops(line, font, font_size, tab_stops);
             max_width = utils::partial_max(max_width, width);
         }
         Dimension::Absolute(max_width)
@@ -200,6 +272,7 @@ impl<'a> Widget for Text<'a> {
 
         let text = &self.text;
         let font_size = self.style.font_size(&ui.theme);
+        let tab_stops = self.style.tab_stops(&ui.theme);
         let num_lines = match self.style.maybe_wrap(&ui.theme) {
             None => text.lines().count(),
             Some(wrap) => match self.get_w(ui) {
@@ -207,10 +280,12 @@ impl<'a> Widget for Text<'a> {
                 Some(max_w) => match wrap {
                     Wrap::Character =>
                         text::line::infos(text, font, font_size)
+                            .tab_stops(tab_stops)
                             .wrap_by_character(max_w)
                             .count(),
                     Wrap::Whitespace =>
                         text::line::infos(text, font, font_size)
+                            .tab_stops(tab_stops)
                             .wrap_by_whitespace(max_w)
                             .count(),
                 },
@@ -228,52 +303,163 @@ impl<'a> Widget for Text<'a> {
 
         let maybe_wrap = style.maybe_wrap(ui.theme());
         let font_size = style.font_size(ui.theme());
+        let overflow = style.overflow(ui.theme());
+        let tab_stops = style.tab_stops(ui.theme());
 
-        let font = match style.font_id(&ui.theme)
+        let font_id = match style.font_id(&ui.theme)
             .or(ui.fonts.ids().next())
-            .and_then(|id| ui.fonts.get(id))
+            .and_then(|id| ui.fonts.get(id).map(|_| id))
         {
-            Some(font) => font,
+            Some(font_id) => font_id,
             None => return,
         };
+        let font = ui.fonts.get(font_id).unwrap();
 
-        // Produces an iterator yielding info for each line within the `text`.
-        let new_line_infos = || match maybe_wrap {
-            None =>
-                text::line::infos(text, font, font_size),
-            Some(Wrap::Character) =>
-                text::line::infos(text, font, font_size).wrap_by_character(rect.w()),
-            Some(Wrap::Whitespace) =>
-                text::line::infos(text, font, font_size).wrap_by_whitespace(rect.w()),
+        // Shorten any lines that exceed the width if necessary.
+        let text = match (maybe_wrap, overflow) {
+            (None, Overflow::Ellipsis(ellipsis)) =>
+                ellipsize(text, font, font_size, rect.w(), ellipsis),
+            _ => std::borrow::Cow::Borrowed(text),
         };
+        let text = &text[..];
+
+        // Retrieve the info for each line within the `text`, reusing the `Ui`'s cached layout if
+        // neither the text nor its style have changed.
+        let justify = style.justify(ui.theme());
+        let key = text::layout_cache::Key::new(text, font_id, font_size, maybe_wrap, rect.w(),
+                                               justify, tab_stops);
+        let new_line_infos = ui.text_line_infos(&key, text).unwrap_or_else(|| Vec::new().into());
 
         // If the string is different, we must update both the string and the line breaks.
         if &state.string[..] != text {
             state.update(|state| {
                 state.string = text.to_owned();
-                state.line_infos = new_line_infos().collect();
+                state.line_infos = new_line_infos;
             });
 
         // Otherwise, we'll check to see if we have to update the line breaks.
-        } else {
-            use utils::write_if_different;
-            use std::borrow::Cow;
-
-            // Compare the line_infos and only collect the new ones if they are different.
-            let maybe_new_line_infos = {
-                let line_infos = &state.line_infos[..];
-                match write_if_different(line_infos, new_line_infos()) {
-                    Cow::Owned(new) => Some(new),
-                    _ => None,
-                }
-            };
-
-            if let Some(new_line_infos) = maybe_new_line_infos {
-                state.update(|state| state.line_infos = new_line_infos);
+        } else if !std::sync::Arc::ptr_eq(&state.line_infos, &new_line_infos) {
+            state.update(|state| state.line_infos = new_line_infos);
+        }
+    }
+
+}
+
+impl Decoration {
+
+    /// Specify the thickness of the line.
+    pub fn thickness(mut self, thickness: Scalar) -> Self {
+        self.thickness = Some(thickness);
+        self
+    }
+
+    /// Specify the color of the line.
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+}
+
+/// A `Rect` and `Color` for each decoration of a block of text, i.e. its background highlights,
+/// underlines, strikethroughs and overlines.
+///
+/// Backgrounds are to be drawn behind the glyphs while the lines are to be drawn in front of them.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Decorations {
+    /// A rectangle behind each non-empty line of text.
+    pub backgrounds: Vec<(Rect, Color)>,
+    /// The underline, strikethrough and overline for each non-empty line of text.
+    pub lines: Vec<(Rect, Color)>,
+}
+
+impl Style {
+
+    /// Whether or not the **Text** has any decorations to draw alongside its glyphs.
+    pub fn has_decorations(&self, theme: &Theme) -> bool {
+        self.background_color(theme).is_some()
+            || self.underline(theme).is_some()
+            || self.strikethrough(theme).is_some()
+            || self.overline(theme).is_some()
+    }
+
+}
+
+/// Produce the `Decorations` for the given lines of text, positioned via the font's vertical
+/// metrics.
+///
+/// `line_rects` is expected to yield the `Rect` of each line as produced by `text::line::rects`,
+/// where the bottom of each `Rect` is the line's baseline.
+pub fn decorations<I>(line_rects: I,
+                      font: &text::Font,
+                      font_size: FontSize,
+                      style: &Style,
+                      theme: &Theme) -> Decorations
+    where I: IntoIterator<Item=Rect>,
+{
+    let color = style.color(theme);
+    let background_color = style.background_color(theme);
+    let v_metrics = font.v_metrics(text::pt_to_scale(font_size));
+    let (ascent, descent) = (v_metrics.ascent as Scalar, v_metrics.descent as Scalar);
+    let default_thickness = (text::pt_to_px(font_size) as Scalar / 16.0).max(1.0);
+
+    // The offsets of each line from the baseline.
+    let line_offsets = [
+        (style.underline(theme), descent / 2.0),
+        (style.strikethrough(theme), ascent * 0.35),
+        (style.overline(theme), ascent),
+    ];
+
+    let mut decorations = Decorations::default();
+    for line_rect in line_rects {
+        if line_rect.w() <= 0.0 {
+            continue;
+        }
+        let baseline = line_rect.bottom();
+
+        if let Some(background_color) = background_color {
+            let y = Range::new(baseline + descent, baseline + ascent);
+            decorations.backgrounds.push((Rect { x: line_rect.x, y: y }, background_color));
+        }
+
+        for &(maybe_decoration, offset) in line_offsets.iter() {
+            if let Some(decoration) = maybe_decoration {
+                let thickness = decoration.thickness.unwrap_or(default_thickness);
+                let color = decoration.color.unwrap_or(color);
+                let y = Range::from_pos_and_len(baseline + offset, thickness);
+                decorations.lines.push((Rect { x: line_rect.x, y: y }, color));
             }
         }
     }
+    decorations
+}
+
+/// Shorten each line within `text` that exceeds `max_width` with the given `ellipsis`.
+fn ellipsize<'a>(text: &'a str,
+                 font: &text::Font,
+                 font_size: FontSize,
+                 max_width: Scalar,
+                 ellipsis: text::Ellipsis) -> std::borrow::Cow<'a, str>
+{
+    use std::borrow::Cow;
+
+    if text::line::infos(text, font, font_size).all(|info| info.width <= max_width) {
+        return Cow::Borrowed(text);
+    }
 
+    let mut string = String::with_capacity(text.len());
+    for info in text::line::infos(text, font, font_size) {
+        let line = &text[info.byte_range()];
+        if info.width <= max_width {
+            string.push_str(line);
+        } else {
+            string.push_str(&text::line::ellipsize(line, font, font_size, max_width, ellipsis));
+        }
+        if let text::line::Break::Newline { byte, len_bytes, .. } = info.end_break {
+            string.push_str(&text[byte..byte + len_bytes]);
+        }
+    }
+    Cow::Owned(string)
 }
 
 impl<'a> Colorable for Text<'a> {
@@ -0,0 +1,406 @@
+//! A widget for displaying read-only text that may be selected by the user.
+
+use {Color, Colorable, FontSize, Positionable, Sizeable, Widget, Ui};
+use event;
+use input;
+use position::{Align, Dimension, Point, Rect, Scalar};
+use std;
+use text;
+use utils;
+use widget;
+use cursor;
+use widget::primitive::text::Wrap;
+
+
+/// A widget for displaying read-only text that may be selected by the user.
+///
+/// Text may be selected by dragging the mouse, by extending an existing selection with
+/// `Shift+Click`, by double clicking a word or by pressing `Ctrl+A` while the widget is capturing
+/// the keyboard.
+///
+/// Unlike the `TextEdit` widget, the text cannot be mutated.
+#[derive(WidgetCommon_)]
+pub struct SelectableText<'a> {
+    #[conrod(common_builder)]
+    common: widget::CommonBuilder,
+    text: &'a str,
+    style: Style,
+}
+
+/// Unique graphical styling for the SelectableText.
+#[derive(Copy, Clone, Debug, Default, PartialEq, WidgetStyle_)]
+pub struct Style {
+    /// The color of the text.
+    #[conrod(default = "theme.label_color")]
+    pub color: Option<Color>,
+    /// The color of the rectangles highlighting the selected text.
+    ///
+    /// If `None`, a translucent highlight of the text color is used.
+    #[conrod(default = "None")]
+    pub selection_color: Option<Option<Color>>,
+    /// The font size for the text.
+    #[conrod(default = "theme.font_size_medium")]
+    pub font_size: Option<FontSize>,
+    /// Whether or not the text should wrap around the width.
+    #[conrod(default = "Some(Wrap::Whitespace)")]
+    pub maybe_wrap: Option<Option<Wrap>>,
+    /// The horizontal alignment of the text.
+    #[conrod(default = "text::Justify::Left")]
+    pub justify: Option<text::Justify>,
+    /// The vertical space between each line of text.
+    #[conrod(default = "1.0")]
+    pub line_spacing: Option<Scalar>,
+    /// The font used for the text.
+    #[conrod(default = "theme.font_id")]
+    pub font_id: Option<Option<text::font::Id>>,
+    /// The positions to which tab characters advance.
+    #[conrod(default = "text::TabStops::default()")]
+    pub tab_stops: Option<text::TabStops>,
+}
+
+widget_ids! {
+    struct Ids {
+        selected_rectangles[],
+        text,
+    }
+}
+
+/// The State of the SelectableText widget that will be cached within the Ui.
+pub struct State {
+    /// The "anchor" of the selection.
+    start: text::cursor::Index,
+    /// The logical cursor position at the other end of the selection.
+    ///
+    /// The selection is empty when `start == end`.
+    end: text::cursor::Index,
+    /// Whether or not the left mouse button is currently dragging out a selection.
+    selecting: bool,
+    /// Information about each line of text.
+    line_infos: std::sync::Arc<[text::line::Info]>,
+    ids: Ids,
+}
+
+
+impl<'a> SelectableText<'a> {
+
+    /// Construct a SelectableText widget.
+    pub fn new(text: &'a str) -> Self {
+        SelectableText {
+            common: widget::CommonBuilder::default(),
+            style: Style::default(),
+            text: text,
+        }
+    }
+
+    /// Specify that the text should not wrap lines around the width.
+    pub fn no_line_wrap(mut self) -> Self {
+        self.style.maybe_wrap = Some(None);
+        self
+    }
+
+    /// Line wrap the text at the beginning of the first word that exceeds the width.
+    pub fn wrap_by_word(mut self) -> Self {
+        self.style.maybe_wrap = Some(Some(Wrap::Whitespace));
+        self
+    }
+
+    /// Line wrap the text at the beginning of the first character that exceeds the width.
+    pub fn wrap_by_character(mut self) -> Self {
+        self.style.maybe_wrap = Some(Some(Wrap::Character));
+        self
+    }
+
+    /// Align the text to the left of its bounding **Rect**'s *x* axis range.
+    pub fn left_justify(self) -> Self {
+        self.justify(text::Justify::Left)
+    }
+
+    /// Align the text to the middle of its bounding **Rect**'s *x* axis range.
+    pub fn center_justify(self) -> Self {
+        self.justify(text::Justify::Center)
+    }
+
+    /// Align the text to the right of its bounding **Rect**'s *x* axis range.
+    pub fn right_justify(self) -> Self {
+        self.justify(text::Justify::Right)
+    }
+
+    /// Specify the font used for displaying the text.
+    pub fn font_id(mut self, font_id: text::font::Id) -> Self {
+        self.style.font_id = Some(Some(font_id));
+        self
+    }
+
+    /// Specify the color of the rectangles highlighting the selected text.
+    pub fn selection_color(mut self, color: Color) -> Self {
+        self.style.selection_color = Some(Some(color));
+        self
+    }
+
+    builder_methods!{
+        pub font_size { style.font_size = Some(FontSize) }
+        pub justify { style.justify = Some(text::Justify) }
+        pub line_spacing { style.line_spacing = Some(Scalar) }
+        pub tab_stops { style.tab_stops = Some(text::TabStops) }
+    }
+
+    /// The `Text` widget used to display the text with the current styling.
+    fn text_widget(&self) -> widget::Text<'a> {
+        let mut text = widget::Text::new(self.text);
+        text.style.font_size = self.style.font_size;
+        text.style.maybe_wrap = self.style.maybe_wrap;
+        text.style.justify = self.style.justify;
+        text.style.line_spacing = self.style.line_spacing;
+        text.style.font_id = self.style.font_id;
+        text.style.tab_stops = self.style.tab_stops;
+        text
+    }
+
+}
+
+impl<'a> Widget for SelectableText<'a> {
+    type State = State;
+    type Style = Style;
+    /// `Some` with the newly selected text whenever the selection changes.
+    ///
+    /// An empty `String` indicates that the selection was cleared.
+    type Event = Option<String>;
+
+    fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
+        let start = text::cursor::Index { line: 0, char: 0 };
+        State {
+            start: start,
+            end: start,
+            selecting: false,
+            line_infos: Vec::new().into(),
+            ids: Ids::new(id_gen),
+        }
+    }
+
+    fn style(&self) -> Self::Style {
+        self.style.clone()
+    }
+
+    /// If no specific width was given, the width of the widest line is used as a default.
+    fn default_x_dimension(&self, ui: &Ui) -> Dimension {
+        self.text_widget().default_x_dimension(ui)
+    }
+
+    /// If no specific height was given, the total height of the text is used as a default.
+    fn default_y_dimension(&self, ui: &Ui) -> Dimension {
+        let text = self.text_widget();
+        match self.get_w(ui) {
+            Some(w) => text.w(w).default_y_dimension(ui),
+            None => text.default_y_dimension(ui),
+        }
+    }
+
+    /// Update the state of the SelectableText.
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        let widget::UpdateArgs { id, state, rect, style, ui, .. } = args;
+        let text = self.text;
+
+        // If we've no font to use for text logic, bail out without updating.
+        let font_id = match style.font_id(&ui.theme)
+            .or(ui.fonts.ids().next())
+            .and_then(|id| ui.fonts.get(id).map(|_| id))
+        {
+            Some(font_id) => font_id,
+            None => return None,
+        };
+
+        let font_size = style.font_size(ui.theme());
+        let maybe_wrap = style.maybe_wrap(ui.theme());
+        let justify = style.justify(ui.theme());
+        let line_spacing = style.line_spacing(ui.theme());
+        let tab_stops = style.tab_stops(ui.theme());
+
+        // The `Text` widget always aligns its text with the top of its `Rect`.
+        let y_align = Align::End;
+
+        // Check to see if the text or its layout has changed since the last update, reusing the
+        // `Ui`'s cached layout where possible.
+        {
+            let key = text::layout_cache::Key::new(text, font_id, font_size, maybe_wrap, rect.w(),
+                                                   justify, tab_stops);
+            let new_line_infos = ui.text_line_infos(&key, text).unwrap_or_else(|| Vec::new().into());
+            if !std::sync::Arc::ptr_eq(&state.line_infos, &new_line_infos) {
+                state.update(|state| state.line_infos = new_line_infos);
+            }
+        }
+
+        let mut start = state.start.clamp_to_lines(state.line_infos.iter().cloned());
+        let mut end = state.end.clamp_to_lines(state.line_infos.iter().cloned());
+        let mut selecting = state.selecting;
+
+        // Find the closest cursor index to the given `xy` position.
+        let closest_cursor_index = |xy: Point, line_infos: &[text::line::Info]| {
+            let font = ui.fonts.get(font_id).unwrap();
+            let xys_per_line = text::cursor::xys_per_line_from_text(text, line_infos, font,
+                                                                    font_size, justify, y_align,
+                                                                    line_spacing, rect)
+                .tab_stops(tab_stops);
+            text::cursor::closest_cursor_index_and_xy(xy, xys_per_line).map(|(idx, _)| idx)
+        };
+
+        for widget_event in ui.widget_input(id).events() {
+            match widget_event {
+
+                // Begin a new selection, or extend the existing selection if `Shift` is down.
+                event::Widget::Press(press) => match press.button {
+                    event::Button::Mouse(input::MouseButton::Left, rel_xy) => {
+                        let abs_xy = utils::vec2_add(rel_xy, rect.xy());
+                        if let Some(idx) = closest_cursor_index(abs_xy, &state.line_infos) {
+                            end = idx;
+                            if !press.modifiers.contains(input::keyboard::ModifierKey::SHIFT) {
+                                start = idx;
+                            }
+                        }
+                        selecting = true;
+                    },
+
+                    // Select all text on Ctrl+a.
+                    event::Button::Keyboard(input::Key::A) => {
+                        if press.modifiers.contains(input::keyboard::ModifierKey::CTRL) {
+                            let line_infos = state.line_infos.iter().cloned();
+                            start = text::cursor::Index { line: 0, char: 0 };
+                            end = text::cursor::index_before_char(line_infos, text.chars().count())
+                                .unwrap_or(start);
+                        }
+                    },
+
+                    _ => (),
+                },
+
+                event::Widget::Release(release) => {
+                    if let event::Button::Mouse(input::MouseButton::Left, _) = release.button {
+                        selecting = false;
+                    }
+                },
+
+                // Extend the selection to the mouse.
+                event::Widget::Drag(drag) if drag.button == input::MouseButton::Left => {
+                    if selecting {
+                        let abs_xy = utils::vec2_add(drag.to, rect.xy());
+                        if let Some(idx) = closest_cursor_index(abs_xy, &state.line_infos) {
+                            end = idx;
+                        }
+                    }
+                },
+
+                // Select the word under the mouse.
+                event::Widget::DoubleClick(double_click) => {
+                    if double_click.button == input::MouseButton::Left {
+                        let abs_xy = utils::vec2_add(double_click.xy, rect.xy());
+                        let word = closest_cursor_index(abs_xy, &state.line_infos)
+                            .and_then(|idx| {
+                                let line_infos = state.line_infos.iter().cloned();
+                                idx.word_range(text, line_infos)
+                            });
+                        if let Some((word_start, word_end)) = word {
+                            start = word_start;
+                            end = word_end;
+                        }
+                    }
+                },
+
+                // Clear the selection when some other widget captures the keyboard.
+                event::Widget::UncapturesInputSource(input::Source::Keyboard) => {
+                    end = start;
+                    selecting = false;
+                },
+
+                _ => (),
+            }
+        }
+
+        if let Some(_) = ui.widget_input(id).mouse() {
+            ui.set_mouse_cursor(cursor::MouseCursor::Text);
+        }
+
+        // Produce the selected text if the selection has changed.
+        let was_selected = state.start != state.end;
+        let is_selected = start != end;
+        let has_changed = state.start != start || state.end != end;
+        let event = if has_changed && (was_selected || is_selected) {
+            let (lo, hi) = (std::cmp::min(start, end), std::cmp::max(start, end));
+            let line_infos = || state.line_infos.iter().cloned();
+            let lo_char = text::glyph::index_after_cursor(line_infos(), lo).unwrap_or(0);
+            let hi_char = text::glyph::index_after_cursor(line_infos(), hi).unwrap_or(0);
+            Some(text.chars().skip(lo_char).take(hi_char - lo_char).collect())
+        } else {
+            None
+        };
+
+        if has_changed {
+            state.update(|state| {
+                state.start = start;
+                state.end = end;
+            });
+        }
+
+        if state.selecting != selecting {
+            state.update(|state| state.selecting = selecting);
+        }
+
+        let color = style.color(ui.theme());
+        let mut text_widget = self.text_widget();
+        text_widget.style.maybe_wrap = Some(maybe_wrap);
+        text_widget
+            .font_id(font_id)
+            .font_size(font_size)
+            .justify(justify)
+            .line_spacing(line_spacing)
+            .tab_stops(tab_stops)
+            .wh(rect.dim())
+            .xy(rect.xy())
+            .parent(id)
+            .graphics_for(id)
+            .color(color)
+            .set(state.ids.text, ui);
+
+        // Draw a `Rectangle` over the selected range of each line.
+        if is_selected {
+            let (start, end) = (std::cmp::min(start, end), std::cmp::max(start, end));
+
+            let selected_rects: Vec<Rect> = {
+                let line_infos = state.line_infos.iter().cloned();
+                let lines = line_infos.clone().map(|info| &text[info.byte_range()]);
+                let line_rects = text::line::rects(line_infos.clone(), font_size, rect,
+                                                   justify, y_align, line_spacing);
+                let lines_with_rects = lines.zip(line_rects.clone());
+                let font = ui.fonts.get(font_id).unwrap();
+                text::line::selected_rects(lines_with_rects, font, font_size, start, end)
+                    .tab_stops(tab_stops)
+                    .collect()
+            };
+
+            // Ensure we have at least as many widgets as selected_rectangles.
+            if state.ids.selected_rectangles.len() < selected_rects.len() {
+                let num_rects = selected_rects.len();
+                let id_gen = &mut ui.widget_id_generator();
+                state.update(|state| state.ids.selected_rectangles.resize(num_rects, id_gen));
+            }
+
+            let selection_color = style.selection_color(ui.theme())
+                .unwrap_or_else(|| color.highlighted().alpha(0.25));
+            let iter = state.ids.selected_rectangles.iter().zip(&selected_rects);
+            for (&selected_rectangle_id, selected_rect) in iter {
+                widget::Rectangle::fill(selected_rect.dim())
+                    .xy(selected_rect.xy())
+                    .color(selection_color)
+                    .graphics_for(id)
+                    .parent(id)
+                    .set(selected_rectangle_id, ui);
+            }
+        }
+
+        event
+    }
+
+}
+
+
+impl<'a> Colorable for SelectableText<'a> {
+    builder_method!(color { style.color = Some(Color) });
+}
@@ -1,6 +1,8 @@
 //! A widget for selecting a single value along some linear range.
 
 use {Color, Colorable, FontSize, Borderable, Labelable, Positionable, Widget};
+use event;
+use input;
 use num::{Float, NumCast, ToPrimitive};
 use position::{Padding, Range, Rect, Scalar};
 use text;
@@ -69,6 +71,8 @@ widget_ids! {
 /// Represents the state of the Slider widget.
 pub struct State {
     ids: Ids,
+    /// The value at the time of the last left press, restored if the drag is cancelled.
+    value_before_drag: Option<f64>,
 }
 
 impl<'a, T> Slider<'a, T> {
@@ -110,6 +114,7 @@ impl<'a, T> Widget for Slider<'a, T>
     fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
         State {
             ids: Ids::new(id_gen),
+            value_before_drag: None,
         }
     }
 
@@ -117,6 +122,10 @@ impl<'a, T> Widget for Slider<'a, T>
         self.style.clone()
     }
 
+    fn is_focusable(&self) -> bool {
+        true
+    }
+
     fn kid_area(&self, args: widget::KidAreaArgs<Self>) -> widget::KidArea {
         const LABEL_PADDING: Scalar = 10.0;
         widget::KidArea {
@@ -166,6 +175,47 @@ impl<'a, T> Widget for Slider<'a, T>
             value
         };
 
+        // Restore the value from before the drag if the drag is cancelled via `Escape`.
+        let mut new_value = new_value;
+        let mut value_before_drag = state.value_before_drag;
+        for widget_event in ui.widget_input(id).events() {
+            match widget_event {
+                event::Widget::Press(press) => {
+                    if let Some(input::MouseButton::Left) = press.mouse().map(|m| m.button) {
+                        value_before_drag = value.to_f64();
+                    }
+                },
+                event::Widget::Release(release) => {
+                    if let Some(input::MouseButton::Left) = release.mouse().map(|m| m.button) {
+                        value_before_drag = None;
+                    }
+                },
+                event::Widget::DragCancelled(cancelled) if cancelled.button == input::MouseButton::Left => {
+                    if let Some(before) = value_before_drag.take() {
+                        new_value = NumCast::from(before).unwrap();
+                    }
+                },
+                _ => (),
+            }
+        }
+        if state.value_before_drag != value_before_drag {
+            state.update(|state| state.value_before_drag = value_before_drag);
+        }
+
+        // While focused, the arrow keys step the value by a hundredth of the range (or a tenth while
+        // `Shift` is held) and `Home` and `End` move it to either end.
+        let new_value = ui.widget_input(id).presses().key().fold(new_value, |value, press| {
+            let step = if press.modifiers.contains(input::keyboard::ModifierKey::SHIFT) { 0.1 } else { 0.01 };
+            let perc = match press.key {
+                input::Key::Right | input::Key::Up => map_range(value, min, max, 0.0, 1.0) + step,
+                input::Key::Left | input::Key::Down => map_range(value, min, max, 0.0, 1.0) - step,
+                input::Key::Home => 0.0,
+                input::Key::End => 1.0,
+                _ => return value,
+            };
+            value_from_perc(clamp(perc, 0.0, 1.0) as f32, min, max)
+        });
+
         // The **Rectangle** for the border.
         let interaction_color = |ui: &::ui::UiCell, color: Color|
             ui.widget_input(id).mouse()
@@ -66,6 +66,9 @@ pub struct Style {
     /// The styling for each `Canvas`.
     #[conrod(default = "widget::canvas::Style::default()")]
     pub canvas: Option<widget::canvas::Style>,
+    /// How tab labels that are wider than their tab are displayed.
+    #[conrod(default = "widget::text::Overflow::Ellipsis(text::Ellipsis::End)")]
+    pub label_overflow: Option<widget::text::Overflow>,
 }
 
 /// The direction in which the tabs will be laid out.
@@ -163,6 +166,7 @@ impl<'a> Tabs<'a> {
         pub starting_tab_idx { maybe_starting_tab_idx = Some(usize) }
         pub label_color { style.label_color = Some(Color) }
         pub label_font_size { style.label_font_size = Some(FontSize) }
+        pub label_overflow { style.label_overflow = Some(widget::text::Overflow) }
     }
 
 }
@@ -266,6 +270,7 @@ impl<'a> Widget for Tabs<'a> {
             let border = canvas_style.border(&ui.theme);
             let border_color = canvas_style.border_color(ui.theme());
             let label_color = style.label_color(ui.theme());
+            let label_overflow = style.label_overflow(ui.theme());
             let mut maybe_selected_tab_idx = state.maybe_selected_tab_idx
                 .or(maybe_starting_tab_idx)
                 .or_else(|| if tabs.len() > 0 { Some(0) } else { None });
@@ -285,6 +290,7 @@ impl<'a> Widget for Tabs<'a> {
                     .border_color(border_color)
                     .label(label)
                     .label_color(label_color)
+                    .label_overflow(label_overflow)
                     .parent(id)
                     .set(tab.button_id, &mut ui)
                     .was_clicked()
@@ -1,9 +1,9 @@
 //! A widget for displaying and mutating a one-line field of text.
 
-use {Color, Colorable, FontSize, Borderable, Positionable, Sizeable, Widget};
-use event;
+use {Color, Colorable, FontSize, Borderable, Labelable, Positionable, Sizeable, Widget};
+use color;
 use input;
-use position::{Range, Rect, Scalar};
+use position::{self, Range, Rect, Scalar};
 use text;
 use widget;
 
@@ -11,12 +11,20 @@ use widget;
 /// form of a `String`.
 ///
 /// It's reaction is triggered upon pressing of the `Enter`/`Return` key.
+///
+/// Given a list of `completions`, a list of them is shown beneath the caret while the text is being
+/// edited. `Up` and `Down` move through the list, `Tab` or `Return` accepts the highlighted
+/// completion and `Escape` dismisses the list until the text is next changed.
 #[derive(WidgetCommon_)]
 pub struct TextBox<'a> {
     #[conrod(common_builder)]
     common: widget::CommonBuilder,
     text: &'a str,
     style: Style,
+    filter: Option<widget::text_edit::Filter<'a>>,
+    validate: Option<&'a Fn(&str) -> Result<(), String>>,
+    placeholder: Option<&'a str>,
+    completions: Option<&'a [String]>,
 }
 
 /// Unique graphical styling for the TextBox.
@@ -49,18 +57,51 @@ pub struct Style {
     /// The font used for the `Text`.
     #[conrod(default = "theme.font_id")]
     pub font_id: Option<Option<text::font::Id>>,
+    /// The character displayed in place of each character of the text, if any.
+    #[conrod(default = "None")]
+    pub mask: Option<Option<char>>,
+    /// Whether or not to show a button that reveals the masked text while it is held down.
+    #[conrod(default = "false")]
+    pub reveal_toggle: Option<bool>,
+    /// The maximum number of characters that the text may contain, if any.
+    #[conrod(default = "None")]
+    pub max_length: Option<Option<usize>>,
+    /// The color of the border and message shown when the text is invalid.
+    #[conrod(default = "color::RED")]
+    pub invalid_color: Option<Color>,
+    /// The font size of the message shown when the text is invalid.
+    #[conrod(default = "theme.font_size_small")]
+    pub invalid_font_size: Option<FontSize>,
+    /// The color of the placeholder text shown while the text is empty.
+    #[conrod(default = "theme.label_color.alpha(0.5)")]
+    pub placeholder_color: Option<Color>,
+    /// The maximum height of the list of completions, beyond which it may be scrolled.
+    #[conrod(default = "200.0")]
+    pub completions_max_height: Option<Scalar>,
 }
 
 widget_ids! {
     struct Ids {
         text_edit,
         rectangle,
+        reveal_button,
+        reveal_icon,
+        invalid_message,
+        completions,
     }
 }
 
 /// The `State` of the `TextBox` widget that will be cached within the `Ui`.
 pub struct State {
     ids: Ids,
+    /// The index of the highlighted completion, if any.
+    completion: Option<usize>,
+    /// Whether or not the completions were dismissed since the text was last changed.
+    completions_dismissed: bool,
+    /// The distance of the caret from the left of the text, beneath which completions are shown.
+    caret_offset: Scalar,
+    /// The char index at which the cursor is placed during the next update.
+    place_cursor: Option<usize>,
 }
 
 impl<'a> TextBox<'a> {
@@ -71,6 +112,10 @@ impl<'a> TextBox<'a> {
             common: widget::CommonBuilder::default(),
             style: Style::default(),
             text: text,
+            filter: None,
+            validate: None,
+            placeholder: None,
+            completions: None,
         }
     }
 
@@ -95,11 +140,68 @@ impl<'a> TextBox<'a> {
         self
     }
 
+    /// Display the given character in place of each character of the text, e.g. `'•'` for
+    /// password fields.
+    ///
+    /// See `TextEdit::mask` for details.
+    pub fn mask(mut self, mask: char) -> Self {
+        self.style.mask = Some(Some(mask));
+        self
+    }
+
+    /// Filter text entered by the user before it is inserted.
+    ///
+    /// See `TextEdit::filter` for details.
+    pub fn filter(mut self, filter: widget::text_edit::Filter<'a>) -> Self {
+        self.filter = Some(filter);
+        self
+    }
+
+    /// Text hinting at what belongs in the `TextBox`, shown while its text is empty.
+    pub fn placeholder(mut self, placeholder: &'a str) -> Self {
+        self.placeholder = Some(placeholder);
+        self
+    }
+
+    /// Do not allow the text to exceed the given number of characters.
+    pub fn max_length(mut self, max_length: usize) -> Self {
+        self.style.max_length = Some(Some(max_length));
+        self
+    }
+
+    /// Validate the text each time the `TextBox` is set.
+    ///
+    /// If the given function returns an `Err`, the `TextBox` border is drawn with the
+    /// `invalid_color` and the error message is displayed beneath it.
+    pub fn validate(mut self, validate: &'a Fn(&str) -> Result<(), String>) -> Self {
+        self.validate = Some(validate);
+        self
+    }
+
+    /// Offer the given completions of the current text while it is being edited.
+    ///
+    /// Accepting a completion replaces the text with it, producing an `Update` followed by a
+    /// `Complete` event.
+    pub fn completions(mut self, completions: &'a [String]) -> Self {
+        self.completions = Some(completions);
+        self
+    }
+
+    /// Show a button to the right of a masked `TextBox` that reveals the text while held down.
+    pub fn reveal_toggle(mut self) -> Self {
+        self.style.reveal_toggle = Some(true);
+        self
+    }
+
     builder_methods!{
         pub text_color { style.text_color = Some(Color) }
         pub font_size { style.font_size = Some(FontSize) }
         pub justify { style.justify = Some(text::Justify) }
         pub pad_text { style.text_padding = Some(Scalar) }
+        pub invalid_color { style.invalid_color = Some(Color) }
+        pub invalid_font_size { style.invalid_font_size = Some(FontSize) }
+        pub placeholder_color { style.placeholder_color = Some(Color) }
+        pub completions_max_height { style.completions_max_height = Some(Scalar) }
     }
 
 }
@@ -111,6 +213,8 @@ pub enum Event {
     Update(String),
     /// The `Return` or `Enter` key was pressed.
     Enter,
+    /// The completion at the given index was accepted.
+    Complete(usize),
 }
 
 impl<'a> Widget for TextBox<'a> {
@@ -121,6 +225,10 @@ impl<'a> Widget for TextBox<'a> {
     fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
         State {
             ids: Ids::new(id_gen),
+            completion: None,
+            completions_dismissed: false,
+            caret_offset: 0.0,
+            place_cursor: None,
         }
     }
 
@@ -131,23 +239,39 @@ impl<'a> Widget for TextBox<'a> {
     /// Update the state of the TextEdit.
     fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
         let widget::UpdateArgs { id, state, rect, style, ui, .. } = args;
-        let TextBox { text, .. } = self;
+        let TextBox { text, filter, validate, placeholder, completions, .. } = self;
 
         let font_size = style.font_size(ui.theme());
         let border = style.border(ui.theme());
         let text_padding = style.text_padding(ui.theme());
         let justify = style.justify(ui.theme());
+        let mask = style.mask(ui.theme());
+        let reveal_toggle = mask.is_some() && style.reveal_toggle(ui.theme());
+
+        // The reveal button occupies a square on the right of the inner rectangle.
+        let reveal_button_side = rect.h() - border * 2.0;
+        let reveal_button_w = if reveal_toggle { reveal_button_side } else { 0.0 };
 
         let text_rect = {
-            let w = rect.x.pad(border + text_padding).len();
+            let w = (rect.x.pad(border + text_padding).len() - reveal_button_w).max(0.0);
             let h = font_size as Scalar + 1.0;
-            let x = Range::new(0.0, w).align_middle_of(rect.x);
+            let x = Range::new(0.0, w).align_middle_of(rect.x.pad_end(reveal_button_w));
             let y = Range::new(0.0, h).align_middle_of(rect.y);
             Rect { x: x, y: y }
         };
 
+        // Check whether or not the current text is valid.
+        let invalid_message = match validate.map(|validate| validate(text)) {
+            Some(Err(message)) => Some(message),
+            _ => None,
+        };
+        let invalid_color = style.invalid_color(ui.theme());
+
         let color = style.color(ui.theme());
-        let border_color = style.border_color(ui.theme());
+        let border_color = match invalid_message {
+            Some(_) => invalid_color,
+            None => style.border_color(ui.theme()),
+        };
         widget::BorderedRectangle::new(rect.dim())
             .xy(rect.xy())
             .graphics_for(id)
@@ -160,34 +284,194 @@ impl<'a> Widget for TextBox<'a> {
         let mut events = Vec::new();
 
         let text_color = style.text_color(ui.theme());
+
+        // The text is revealed while the reveal button is held down.
+        let revealed = reveal_toggle && ui.widget_input(state.ids.reveal_button).mouse()
+            .map_or(false, |mouse| mouse.buttons.left().is_down());
+        if reveal_toggle {
+            widget::Button::new()
+                .w_h(reveal_button_side, reveal_button_side)
+                .mid_right_with_margin_on(id, border)
+                .color(color)
+                .border(0.0)
+                .parent(id)
+                .set(state.ids.reveal_button, ui);
+            let icon_radius = reveal_button_side / 6.0;
+            let icon = if revealed {
+                widget::Circle::fill(icon_radius)
+            } else {
+                widget::Circle::outline(icon_radius)
+            };
+            icon.middle_of(state.ids.reveal_button)
+                .color(text_color)
+                .graphics_for(state.ids.reveal_button)
+                .set(state.ids.reveal_icon, ui);
+        }
+
         let font_id = style.font_id(&ui.theme).or(ui.fonts.ids().next());
-        if let Some(new_string) = widget::TextEdit::new(text)
+        let mask = if revealed { None } else { mask };
+        let text_edit_events = widget::TextEdit::new(text)
             .and_then(font_id, widget::TextEdit::font_id)
+            .and_then(mask, widget::TextEdit::mask)
+            .and_then(filter, widget::TextEdit::filter)
+            .and_then(placeholder, widget::TextEdit::placeholder)
+            .placeholder_color(style.placeholder_color(ui.theme()))
+            .and_then(style.max_length(ui.theme()), widget::TextEdit::max_length)
             .wh(text_rect.dim())
             .xy(text_rect.xy())
             .font_size(font_size)
             .color(text_color)
             .justify(justify)
+            .single_line(true)
+            .and_then(state.place_cursor, widget::TextEdit::place_cursor)
             .parent(id)
-            .set(state.ids.text_edit, ui)
-        {
-            events.push(Event::Update(new_string));
+            .set(state.ids.text_edit, ui);
+
+        // Produce an `Enter` event for any `Enter`/`Return` presses, following any update.
+        let mut entered = false;
+        let mut text_changed = false;
+        for text_edit_event in text_edit_events {
+            match text_edit_event {
+                widget::text_edit::Event::Update(new_string) => {
+                    text_changed = true;
+                    events.push(Event::Update(new_string));
+                },
+                widget::text_edit::Event::Submit => entered = true,
+                _ => (),
+            }
         }
 
-        // Produce an event for any `Enter`/`Return` presses.
-        //
-        // TODO: We should probably be doing this via the `TextEdit` widget.
-        for widget_event in ui.widget_input(state.ids.text_edit).events() {
-            match widget_event {
-                event::Widget::Press(press) => match press.button {
-                    event::Button::Keyboard(key) => match key {
-                        input::Key::Return => events.push(Event::Enter),
-                        _ => (),
+        // Navigate the completions while the text is being edited.
+        let completions = completions.unwrap_or(&[]);
+        let num_completions = completions.len();
+        let capturing_keyboard = ui.global_input().current.widget_capturing_keyboard;
+        let editing = capturing_keyboard == Some(state.ids.text_edit);
+        let mut completion = state.completion.and_then(|i| if i < num_completions { Some(i) } else { None });
+        let mut dismissed = state.completions_dismissed && !text_changed;
+        let mut accepted = None;
+        if editing && num_completions > 0 && !dismissed {
+            for press in ui.widget_input(state.ids.text_edit).presses().key() {
+                match press.key {
+                    input::Key::Down => {
+                        completion = Some(completion.map_or(0, |i| (i + 1) % num_completions));
+                    },
+                    input::Key::Up => {
+                        let last = num_completions - 1;
+                        completion = Some(completion.map_or(last, |i| if i == 0 { last } else { i - 1 }));
                     },
+                    input::Key::Tab => accepted = Some(completion.unwrap_or(0)),
+                    // `Return` only accepts a highlighted completion, otherwise entering the text.
+                    input::Key::Return => if completion.is_some() {
+                        accepted = completion;
+                        entered = false;
+                    },
+                    input::Key::Escape => dismissed = true,
                     _ => (),
-                },
-                _ => (),
+                }
+            }
+        }
+
+        // Accepting a completion via `Tab` should not move the keyboard focus.
+        if accepted.is_some() {
+            ui.cancel_focus_traversal();
+        }
+
+        // Show the completions in a floating list beneath the caret. The list remains while one of
+        // its items is being clicked.
+        let choosing = capturing_keyboard.map_or(false, |capturing| {
+            ui.widget_graph().does_recursive_depth_edge_exist(state.ids.completions, capturing)
+        });
+        let mut caret_offset = state.caret_offset;
+        if (editing || choosing) && num_completions > 0 && !dismissed && accepted.is_none() {
+            if let (true, Some(caret_rect)) = (editing, ui.ime_cursor_rect()) {
+                caret_offset = caret_rect.left() - text_rect.left();
             }
+            let item_h = rect.h();
+            let list_h = style.completions_max_height(ui.theme()).min(num_completions as Scalar * item_h);
+            let list_x = text_rect.left() + caret_offset + rect.w() / 2.0;
+            let list_y = rect.bottom() - list_h / 2.0;
+            let (mut list_events, scrollbar) = widget::ListSelect::single(num_completions)
+                .flow_down()
+                .item_size(item_h)
+                .scrollbar_on_top()
+                .scrollbar_color(style.border_color(ui.theme()))
+                .w_h(rect.w(), list_h)
+                .x_y(list_x, list_y)
+                .floating(true)
+                .parent(id)
+                .set(state.ids.completions, ui);
+
+            while let Some(event) = list_events.next(ui, |i| Some(i) == completion) {
+                use widget::list_select::Event;
+                match event {
+
+                    // Instantiate a `Button` for each completion.
+                    Event::Item(item) => {
+                        let item_color = if Some(item.i) == completion { color.highlighted() } else { color };
+                        let label_x = position::Relative::Place(position::Place::Start(Some(text_padding)));
+                        let button = widget::Button::new()
+                            .label(&completions[item.i])
+                            .and_then(font_id, widget::Button::label_font_id)
+                            .label_font_size(font_size)
+                            .label_color(text_color)
+                            .left_justify_label()
+                            .label_x(label_x)
+                            .color(item_color)
+                            .border(0.0)
+                            .focusable(false);
+                        item.set(button, ui);
+                    },
+
+                    // A completion was clicked.
+                    Event::Selection(i) => accepted = Some(i),
+
+                    _ => (),
+                }
+            }
+
+            if let Some(scrollbar) = scrollbar {
+                scrollbar.set(ui);
+            }
+        }
+
+        // Replace the text with the accepted completion, placing the cursor at its end.
+        let mut place_cursor = None;
+        if let Some(i) = accepted {
+            events.push(Event::Update(completions[i].clone()));
+            events.push(Event::Complete(i));
+            place_cursor = Some(completions[i].chars().count());
+            completion = None;
+            dismissed = true;
+        }
+
+        if entered {
+            events.push(Event::Enter);
+        }
+
+        if state.completion != completion
+        || state.completions_dismissed != dismissed
+        || state.caret_offset != caret_offset
+        || state.place_cursor != place_cursor {
+            state.update(|state| {
+                state.completion = completion;
+                state.completions_dismissed = dismissed;
+                state.caret_offset = caret_offset;
+                state.place_cursor = place_cursor;
+            });
+        }
+
+        // Display the message describing why the text is invalid beneath the `TextBox`.
+        if let Some(message) = invalid_message {
+            let invalid_font_size = style.invalid_font_size(ui.theme());
+            widget::Text::new(&message)
+                .and_then(font_id, widget::Text::font_id)
+                .font_size(invalid_font_size)
+                .color(invalid_color)
+                .down_from(id, border + text_padding)
+                .align_left_of(id)
+                .parent(id)
+                .graphics_for(id)
+                .set(state.ids.invalid_message, ui);
         }
 
         events
@@ -1,7 +1,9 @@
 //! A widget for displaying and mutating multi-line text, given as a `String`.
 
 use {Color, Colorable, FontSize, Positionable, Sizeable, Widget, Ui};
+use color;
 use event;
+use fnv;
 use input;
 use position::{Align, Dimension, Point, Range, Rect, Scalar};
 use std;
@@ -22,6 +24,13 @@ pub struct TextEdit<'a> {
     common: widget::CommonBuilder,
     text: &'a str,
     style: Style,
+    clear_history: bool,
+    place_cursor: Option<usize>,
+    filter: Option<Filter<'a>>,
+    placeholder: Option<&'a str>,
+    highlight: Option<&'a Fn(&str) -> Vec<(std::ops::Range<usize>, Color)>>,
+    search: Option<Search<'a>>,
+    search_action: Option<SearchAction<'a>>,
 }
 
 /// Unique graphical styling for the TextEdit.
@@ -51,13 +60,49 @@ pub struct Style {
     /// The font used for the `Text`.
     #[conrod(default = "theme.font_id")]
     pub font_id: Option<Option<text::font::Id>>,
+    /// The positions to which tab characters advance.
+    #[conrod(default = "text::TabStops::default()")]
+    pub tab_stops: Option<text::TabStops>,
+    /// The maximum number of edits that may be undone.
+    #[conrod(default = "100")]
+    pub undo_limit: Option<usize>,
+    /// The character displayed in place of each character of the text, if any.
+    #[conrod(default = "None")]
+    pub mask: Option<Option<char>>,
+    /// The maximum number of characters that the text may contain, if any.
+    #[conrod(default = "None")]
+    pub max_length: Option<Option<usize>>,
+    /// The color of the placeholder text shown while the text is empty.
+    #[conrod(default = "theme.label_color.alpha(0.5)")]
+    pub placeholder_color: Option<Color>,
+    /// Whether or not a new line begins with the indentation of the line on which `Return` was
+    /// pressed.
+    #[conrod(default = "false")]
+    pub auto_indent: Option<bool>,
+    /// Whether or not the text is restricted to a single line.
+    ///
+    /// If `true`, pressing `Return` produces an `Event::Submit` rather than inserting a newline
+    /// and newlines are removed from entered text.
+    ///
+    /// Otherwise, pressing `Tab` inserts a tab rather than moving the keyboard focus to the next
+    /// widget. `Shift+Tab` and `Ctrl+Tab` move the focus in either case.
+    #[conrod(default = "false")]
+    pub single_line: Option<bool>,
+    /// The color of the highlight behind each match of the `Search`.
+    #[conrod(default = "color::YELLOW.alpha(0.35)")]
+    pub search_match_color: Option<Color>,
 }
 
 widget_ids! {
     struct Ids {
         selected_rectangles[],
+        search_matches[],
         text,
+        text_runs[],
+        placeholder,
         cursor,
+        drop_cursor,
+        preedit_underlines[],
     }
 }
 
@@ -67,18 +112,148 @@ pub struct State {
     /// Track whether some sort of dragging is currently occurring.
     drag: Option<Drag>,
     /// Information about each line of text.
-    line_infos: Vec<text::line::Info>,
+    line_infos: std::sync::Arc<[text::line::Info]>,
+    /// The edits that may be undone and redone.
+    history: History,
+    /// The pre-edit text of an IME composition that is in progress.
+    preedit: Option<event::Preedit>,
     ids: Ids,
 }
 
+/// The text displayed while an IME composition is in progress, in which the pre-edit text replaces
+/// the selection at the cursor.
+struct Composition {
+    text: String,
+    line_infos: Vec<text::line::Info>,
+    /// The range of chars within `text` occupied by the pre-edit text.
+    preedit: std::ops::Range<usize>,
+    /// The char index of the IME's caret within `text`.
+    caret: usize,
+}
+
+/// The edits made to the text of a `TextEdit` that may be undone and redone.
+///
+/// The history is cleared if the text given to the `TextEdit` differs from the text produced by
+/// its most recent edit, i.e. if the text is replaced from outside of the `TextEdit`. The history
+/// may be read beforehand via `State::history`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct History {
+    undo: Vec<Edit>,
+    redo: Vec<Edit>,
+    /// A hash of the text that results from the most recent edit.
+    text_hash: u64,
+    /// Whether or not the most recent edit may be extended by further typing.
+    coalesce: bool,
+}
+
+/// A single change to the text of a `TextEdit`, in which a range of text is replaced.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Edit {
+    /// The char index at which the replaced range starts.
+    pub start: usize,
+    /// The text that was removed.
+    pub removed: String,
+    /// The text that was inserted in its place.
+    pub inserted: String,
+    /// The cursor prior to the edit, restored when the edit is undone.
+    pub cursor_before: Cursor,
+    /// The cursor following the edit, restored when the edit is redone.
+    pub cursor_after: Cursor,
+}
+
 /// Track whether some sort of dragging is currently occurring.
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub enum Drag {
     /// The drag is currently selecting a range of text.
     Selecting,
     /// The drag is moving a selection of text.
-    #[allow(dead_code)] // TODO: Implement this.
-    MoveSelection,
+    ///
+    /// The selection is moved to the `drop` position when the mouse is released, or copied there
+    /// if `Ctrl` is held.
+    MoveSelection {
+        /// The position at which the selected text will be dropped, if the mouse has moved.
+        drop: Option<text::cursor::Index>,
+    },
+}
+
+/// The events produced by the `TextEdit`, in the order in which they occurred.
+///
+/// Char indices refer to the text as it was following the preceding events.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Event {
+    /// The given text was inserted at the given char index.
+    Insert {
+        /// The char index at which the text was inserted.
+        at: usize,
+        /// The inserted text.
+        text: String,
+    },
+    /// The given range of chars was removed.
+    Delete {
+        /// The range of chars that was removed.
+        range: std::ops::Range<usize>,
+    },
+    /// The given range of chars was replaced with the given text.
+    Replace {
+        /// The range of chars that was replaced.
+        range: std::ops::Range<usize>,
+        /// The text that replaced the range.
+        text: String,
+    },
+    /// The cursor moved to the given char index, leaving no text selected.
+    CursorMoved(usize),
+    /// The selection changed.
+    SelectionChanged {
+        /// The char index of the "anchor" of the selection.
+        start: usize,
+        /// The char index of the logical cursor position at the other end of the selection.
+        end: usize,
+    },
+    /// `Return` was pressed while the `TextEdit` is restricted to a single line.
+    Submit,
+    /// The `TextEdit` began capturing the keyboard.
+    FocusGained,
+    /// The `TextEdit` stopped capturing the keyboard.
+    FocusLost,
+    /// The complete text following all other events.
+    ///
+    /// This is always the last event and is only produced if the text was changed.
+    Update(String),
+}
+
+/// Filters text entered into a `TextEdit` by typing, pasting or composing via an IME.
+///
+/// The filter may reject the entered text or transform it before it is inserted.
+#[derive(Copy, Clone)]
+pub enum Filter<'a> {
+    /// Only accept ASCII digits.
+    Numeric,
+    /// Only accept characters for which the given function returns `true`, e.g.
+    /// `char::is_alphanumeric`.
+    Chars(fn(char) -> bool),
+    /// Produce the text to insert in place of the given entered text, or `None` to reject it.
+    Custom(&'a Fn(&str) -> Option<String>),
+}
+
+/// A query for which each match within the text of a `TextEdit` is highlighted.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Search<'a> {
+    /// The text to search for.
+    pub query: &'a str,
+    /// Whether or not matches must have the same case as the `query`.
+    pub case_sensitive: bool,
+    /// Whether or not matches must not be directly preceded or followed by a word character, i.e.
+    /// an alphanumeric character or `_`.
+    pub whole_word: bool,
+}
+
+/// An action performed upon the matches of the `Search` during a single update.
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum SearchAction<'a> {
+    Next,
+    Prev,
+    Replace(&'a str),
+    ReplaceAll(&'a str),
 }
 
 /// The position of the `Cursor` over the text.
@@ -107,6 +282,13 @@ impl<'a> TextEdit<'a> {
             common: widget::CommonBuilder::default(),
             style: Style::default(),
             text: text,
+            clear_history: false,
+            place_cursor: None,
+            filter: None,
+            placeholder: None,
+            highlight: None,
+            search: None,
+            search_action: None,
         }
     }
 
@@ -167,6 +349,111 @@ impl<'a> TextEdit<'a> {
         self
     }
 
+    /// Filter text entered by the user before it is inserted.
+    ///
+    /// Entered characters that are not accepted by the filter are discarded.
+    pub fn filter(mut self, filter: Filter<'a>) -> Self {
+        self.filter = Some(filter);
+        self
+    }
+
+    /// Text hinting at what belongs in the `TextEdit`, shown while its text is empty.
+    ///
+    /// The placeholder remains visible while the `TextEdit` captures the keyboard, until some text
+    /// is entered.
+    pub fn placeholder(mut self, placeholder: &'a str) -> Self {
+        self.placeholder = Some(placeholder);
+        self
+    }
+
+    /// Color the text using the given function.
+    ///
+    /// The function is called with the text of each visible line and produces the byte ranges
+    /// within the line that should be drawn with a color other than the `TextEdit`'s `color`.
+    /// Only the visible lines are laid out and drawn, so this remains cheap for very long texts.
+    pub fn highlight(mut self, highlight: &'a Fn(&str) -> Vec<(std::ops::Range<usize>, Color)>) -> Self {
+        self.highlight = Some(highlight);
+        self
+    }
+
+    /// Do not allow the text to exceed the given number of characters.
+    ///
+    /// Entered text that would exceed the limit is truncated.
+    pub fn max_length(mut self, max_length: usize) -> Self {
+        self.style.max_length = Some(Some(max_length));
+        self
+    }
+
+    /// Display the given character in place of each character of the text, e.g. `'•'` for
+    /// password fields.
+    ///
+    /// The text itself is left untouched, however the caret and selection are positioned over the
+    /// masked text and the text may not be copied or cut to the clipboard.
+    pub fn mask(mut self, mask: char) -> Self {
+        self.style.mask = Some(Some(mask));
+        self
+    }
+
+    /// Highlight each match of the given `Search` within the text.
+    ///
+    /// The search is ignored while the text is masked.
+    pub fn search(mut self, search: Search<'a>) -> Self {
+        self.search = Some(search);
+        self
+    }
+
+    /// Select the first match of the `search` following the cursor during this update, wrapping
+    /// around to the first match within the text.
+    pub fn find_next(mut self) -> Self {
+        self.search_action = Some(SearchAction::Next);
+        self
+    }
+
+    /// Select the last match of the `search` preceding the cursor during this update, wrapping
+    /// around to the last match within the text.
+    pub fn find_prev(mut self) -> Self {
+        self.search_action = Some(SearchAction::Prev);
+        self
+    }
+
+    /// Replace the selected match of the `search` with the given text during this update and
+    /// select the following match.
+    ///
+    /// If no match is selected, the next match is selected instead so that it may be reviewed
+    /// before being replaced.
+    ///
+    /// The replacement is filtered and limited to the `max_length` in the same manner as entered
+    /// text.
+    pub fn replace(mut self, replacement: &'a str) -> Self {
+        self.search_action = Some(SearchAction::Replace(replacement));
+        self
+    }
+
+    /// Replace every match of the `search` with the given text during this update.
+    ///
+    /// All replacements are undone together.
+    ///
+    /// The replacement is filtered in the same manner as entered text. No replacements are made if
+    /// the resulting text would exceed the `max_length`.
+    pub fn replace_all(mut self, replacement: &'a str) -> Self {
+        self.search_action = Some(SearchAction::ReplaceAll(replacement));
+        self
+    }
+
+    /// Discard the undo and redo history during this update.
+    pub fn clear_history(mut self) -> Self {
+        self.clear_history = true;
+        self
+    }
+
+    /// Place the cursor before the char at the given index during this update.
+    ///
+    /// An index beyond the end of the text places the cursor at the end.
+    pub fn place_cursor(mut self, char_idx: usize) -> Self {
+        self.place_cursor = Some(char_idx);
+        self
+    }
+
     builder_methods!{
         pub font_size { style.font_size = Some(FontSize) }
         pub justify { style.justify = Some(text::Justify) }
@@ -174,6 +461,12 @@ impl<'a> TextEdit<'a> {
         pub line_wrap { style.line_wrap = Some(Wrap) }
         pub line_spacing { style.line_spacing = Some(Scalar) }
         pub restrict_to_height { style.restrict_to_height = Some(bool) }
+        pub tab_stops { style.tab_stops = Some(text::TabStops) }
+        pub undo_limit { style.undo_limit = Some(usize) }
+        pub placeholder_color { style.placeholder_color = Some(Color) }
+        pub auto_indent { style.auto_indent = Some(bool) }
+        pub single_line { style.single_line = Some(bool) }
+        pub search_match_color { style.search_match_color = Some(Color) }
     }
 
 }
@@ -181,17 +474,15 @@ impl<'a> TextEdit<'a> {
 impl<'a> Widget for TextEdit<'a> {
     type State = State;
     type Style = Style;
-    // TODO: We should create a more specific `Event` type that:
-    // - Allows for mutating an existing `String` directly
-    // - Enumerates possible mutations (i.e. InsertChar, RemoveCharRange, etc).
-    // - Enumerates cursor movement and range selection.
-    type Event = Option<String>;
+    type Event = Vec<Event>;
 
     fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
         State {
             cursor: Cursor::Idx(text::cursor::Index { line: 0, char: 0 }),
             drag: None,
-            line_infos: Vec::new(),
+            line_infos: Vec::new().into(),
+            history: History::default(),
+            preedit: None,
             ids: Ids::new(id_gen),
         }
     }
@@ -200,6 +491,10 @@ impl<'a> Widget for TextEdit<'a> {
         self.style.clone()
     }
 
+    fn is_focusable(&self) -> bool {
+        true
+    }
+
     fn default_y_dimension(&self, ui: &Ui) -> Dimension {
         // If the user has specified `restrict_to_height = true`, then we should infer the height
         // using the previous widget as is the default case.
@@ -217,17 +512,20 @@ impl<'a> Widget for TextEdit<'a> {
             None => return Dimension::Absolute(0.0),
         };
 
-        let text = &self.text;
+        let text = &mask_text(self.text, self.style.mask(&ui.theme));
         let font_size = self.style.font_size(&ui.theme);
+        let tab_stops = self.style.tab_stops(&ui.theme);
         let num_lines = match self.get_w(ui) {
             None => text.lines().count(),
             Some(max_w) => match self.style.line_wrap(&ui.theme) {
                 Wrap::Character =>
                     text::line::infos(text, font, font_size)
+                        .tab_stops(tab_stops)
                         .wrap_by_character(max_w)
                         .count(),
                 Wrap::Whitespace =>
                     text::line::infos(text, font, font_size)
+                        .tab_stops(tab_stops)
                         .wrap_by_whitespace(max_w)
                         .count(),
             },
@@ -240,7 +538,10 @@ impl<'a> Widget for TextEdit<'a> {
     /// Update the state of the TextEdit.
     fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
         let widget::UpdateArgs { id, state, rect, style, ui, .. } = args;
-        let TextEdit { text, .. } = self;
+        let TextEdit {
+            text, clear_history, place_cursor, filter, placeholder, highlight, search,
+            search_action, ..
+        } = self;
         let mut text = std::borrow::Cow::Borrowed(text);
 
         // Retrieve the `font_id`, as long as a valid `Font` for it still exists.
@@ -251,7 +552,7 @@ impl<'a> Widget for TextEdit<'a> {
             .and_then(|id| ui.fonts.get(id).map(|_| id))
         {
             Some(font_id) => font_id,
-            None => return None,
+            None => return Vec::new(),
         };
 
         let font_size = style.font_size(ui.theme());
@@ -260,17 +561,34 @@ impl<'a> Widget for TextEdit<'a> {
         let y_align = style.y_align(ui.theme());
         let line_spacing = style.line_spacing(ui.theme());
         let restrict_to_height = style.restrict_to_height(ui.theme());
+        let tab_stops = style.tab_stops(ui.theme());
+        let undo_limit = style.undo_limit(ui.theme());
+        // All layout and cursor positioning is performed over the text as it is displayed. As
+        // masking replaces each `char` with another, the `char` indices of both texts are the same.
+        let mask = style.mask(ui.theme());
+        let max_length = style.max_length(ui.theme());
+        let single_line = style.single_line(ui.theme());
+        // Searching masked text would reveal its contents.
+        let search = if mask.is_none() { search } else { None };
+
+        // Discard the history if requested or if the text was replaced from outside.
+        if clear_history || !state.history.applies_to(&text) {
+            if state.history != History::default() {
+                state.update(|state| state.history = History::default());
+            }
+        }
 
         /// Returns an iterator yielding the `text::line::Info` for each line in the given text
         /// with the given styling.
-        type LineInfos<'a> = text::line::Infos<'a, text::line::NextBreakFnPtr>;
+        type LineInfos<'a> = text::line::Infos<'a, text::line::NextBreakBy>;
         fn line_infos<'a>(text: &'a str,
                           font: &'a text::Font,
                           font_size: FontSize,
                           line_wrap: Wrap,
+                          tab_stops: text::TabStops,
                           max_width: Scalar) -> LineInfos<'a>
         {
-            let infos = text::line::infos(text, font, font_size);
+            let infos = text::line::infos(text, font, font_size).tab_stops(tab_stops);
             match line_wrap {
                 Wrap::Whitespace => infos.wrap_by_whitespace(max_width),
                 Wrap::Character => infos.wrap_by_character(max_width),
@@ -278,18 +596,18 @@ impl<'a> Widget for TextEdit<'a> {
         }
 
         // Check to see if the given text has changed since the last time the widget was updated.
+        //
+        // The layout is retrieved from the `Ui`'s cache, so this is cheap for unchanged text.
+        //
+        // The masked text is produced here and following each edit, and is otherwise borrowed via
+        // `shown_text`.
+        let mut masked = masked_text(&text, mask);
         {
-            let maybe_new_line_infos = {
-                let line_info_slice = &state.line_infos[..];
-                let font = ui.fonts.get(font_id).unwrap();
-                let new_line_infos = line_infos(&text, font, font_size, line_wrap, rect.w());
-                match utils::write_if_different(line_info_slice, new_line_infos) {
-                    std::borrow::Cow::Owned(new) => Some(new),
-                    _ => None,
-                }
-            };
-
-            if let Some(new_line_infos) = maybe_new_line_infos {
+            let shown_text = shown_text(&text, &masked);
+            let key = text::layout_cache::Key::new(shown_text, font_id, font_size, Some(line_wrap),
+                                                   rect.w(), justify, tab_stops.clone());
+            let new_line_infos = ui.text_line_infos(&key, shown_text).unwrap_or_else(|| Vec::new().into());
+            if !std::sync::Arc::ptr_eq(&state.line_infos, &new_line_infos) {
                 state.update(|state| state.line_infos = new_line_infos);
             }
         }
@@ -313,6 +631,17 @@ impl<'a> Widget for TextEdit<'a> {
             },
         }
 
+        // Place the cursor if requested.
+        if let Some(char_idx) = place_cursor {
+            let char_idx = std::cmp::min(char_idx, text.chars().count());
+            let line_infos = state.line_infos.iter().cloned();
+            if let Some(idx) = text::cursor::index_before_char(line_infos, char_idx) {
+                if state.cursor != Cursor::Idx(idx) {
+                    state.update(|state| state.cursor = Cursor::Idx(idx));
+                }
+            }
+        }
+
         // Find the position of the cursor at the given index over the given text.
         let cursor_xy_at = |cursor_idx: text::cursor::Index,
                             text: &str,
@@ -322,7 +651,8 @@ impl<'a> Widget for TextEdit<'a> {
         {
             let xys_per_line = text::cursor::xys_per_line_from_text(text, line_infos, font,
                                                                     font_size, justify, y_align,
-                                                                    line_spacing, rect);
+                                                                    line_spacing, rect)
+                .tab_stops(tab_stops);
             text::cursor::xy_at(xys_per_line, cursor_idx)
         };
 
@@ -337,7 +667,8 @@ impl<'a> Widget for TextEdit<'a> {
         {
             let xys_per_line = text::cursor::xys_per_line_from_text(text, line_infos, font,
                                                                     font_size, justify, y_align,
-                                                                    line_spacing, rect);
+                                                                    line_spacing, rect)
+                .tab_stops(tab_stops);
             text::cursor::closest_cursor_index_and_xy(xy, xys_per_line)
         };
 
@@ -350,7 +681,8 @@ impl<'a> Widget for TextEdit<'a> {
         {
             let mut xys_per_line = text::cursor::xys_per_line_from_text(text, line_infos, font,
                                                                         font_size, justify, y_align,
-                                                                        line_spacing, rect);
+                                                                        line_spacing, rect)
+                .tab_stops(tab_stops);
             xys_per_line.nth(line_idx).and_then(|(line_xs,_)| {
                 let (char_idx,_) = text::cursor::closest_cursor_index_on_line(x_pos,line_xs);
                 Some(text::cursor::Index { line: line_idx, char: char_idx })
@@ -359,23 +691,80 @@ impl<'a> Widget for TextEdit<'a> {
 
         let mut cursor = state.cursor;
         let mut drag = state.drag;
+        let mut events = Vec::new();
+
+        // Pass the given entered `string` through the `filter`, removing newlines if the text is
+        // restricted to a single line.
+        //
+        // Returns `None` if the `string` was rejected. An empty `string` is never rejected, as no
+        // text is entered.
+        let filter_text = |string: &str| -> Option<String> {
+            if string.is_empty() {
+                return Some(String::new());
+            }
+            let mut string = match filter {
+                Some(filter) => match filter.apply(string) {
+                    Some(string) => string,
+                    None => return None,
+                },
+                None => string.to_string(),
+            };
+            if single_line {
+                string.retain(|ch| ch != '\n' && ch != '\r');
+            }
+            Some(string)
+        };
+
+        // Truncate the given `string` so that replacing `replaced_len` chars of the given `text`
+        // with it does not exceed the `max_length`.
+        let truncate_to_max_length = |string: String, replaced_len: usize, text: &str| -> String {
+            match max_length {
+                Some(max_length) => {
+                    let remaining_len = text.chars().count() - replaced_len;
+                    let available = max_length.saturating_sub(remaining_len);
+                    if string.chars().count() > available {
+                        string.chars().take(available).collect()
+                    } else {
+                        string
+                    }
+                },
+                None => string,
+            }
+        };
 
         // Insert the given `string` at the given `cursor` position within the given `text`.
         //
-        // Produces the resulting text, cursor position and `line::Info`s for the new text.
+        // The `string` is first passed through the `filter` and truncated to the `max_length`.
+        //
+        // Produces the resulting text, its masked text, cursor position, `line::Info`s for the new
+        // text, the range of chars that were replaced and the text that was inserted in their
+        // place.
         //
-        // Returns `None` if the new text would exceed the height restriction.
+        // Returns `None` if the `string` was rejected or if the new text would exceed the height
+        // restriction.
         let insert_text = |string: &str,
                            cursor: Cursor,
                            text: &str,
                            infos: &[text::line::Info],
                            font: &text::Font|
-            -> Option<(String, Cursor, std::vec::Vec<text::line::Info>)>
+            -> Option<(String, Option<String>, Cursor, std::vec::Vec<text::line::Info>, std::ops::Range<usize>, String)>
         {
+            let string = match filter_text(string) {
+                Some(string) => string,
+                None => return None,
+            };
+            let replaced_len = cursor_char_range(cursor, infos).map_or(0, |r| r.len());
+            let string = truncate_to_max_length(string, replaced_len, text);
+
+            if string.is_empty() {
+                return None;
+            }
+
+            let string = &string[..];
             let string_char_count = string.chars().count();
 
             // Construct the new text with the new string inserted at the cursor.
-            let (new_text, new_cursor_char_idx): (String, usize) = {
+            let (new_text, new_cursor_char_idx, replaced): (String, usize, std::ops::Range<usize>) = {
                 let (cursor_start, cursor_end) = match cursor {
                     Cursor::Idx(idx) => (idx, idx),
                     Cursor::Selection { start, end } =>
@@ -396,12 +785,14 @@ impl<'a> Widget for TextEdit<'a> {
                     .chain(string.chars())
                     .chain(text.chars().skip(end_idx))
                     .collect();
-                (new_text, new_cursor_char_idx)
+                (new_text, new_cursor_char_idx, start_idx..end_idx)
             };
 
             // Calculate the new `line_infos` for the `new_text`.
+            let new_masked = masked_text(&new_text, mask);
             let new_line_infos: Vec<_> = {
-                line_infos(&new_text, font, font_size, line_wrap, rect.w()).collect()
+                let new_shown_text = shown_text(&new_text, &new_masked);
+                line_infos(new_shown_text, font, font_size, line_wrap, tab_stops, rect.w()).collect()
             };
 
             // Check that the new text would not exceed the `inner_rect` bounds.
@@ -419,12 +810,16 @@ impl<'a> Widget for TextEdit<'a> {
                         })
                 };
 
-                Some((new_text, Cursor::Idx(new_cursor_idx), new_line_infos))
+                Some((new_text, new_masked, Cursor::Idx(new_cursor_idx), new_line_infos, replaced,
+                      string.to_string()))
             } else {
                 None
             }
         };
 
+        // Whether or not a `Tab` press was used to insert a tab rather than to move the focus.
+        let mut consumed_tab = false;
+
         // Check for the following events:
         // - `Text` events for receiving new text.
         // - Left mouse `Press` events for either:
@@ -443,19 +838,33 @@ impl<'a> Widget for TextEdit<'a> {
                         let abs_xy = utils::vec2_add(rel_xy, rect.xy());
                         let infos = &state.line_infos;
                         let font = ui.fonts.get(font_id).unwrap();
-                        let closest = closest_cursor_index_and_xy(abs_xy, &text, infos, font);
-                        if let Some((closest_cursor, _)) = closest {
-                            cursor = Cursor::Idx(closest_cursor);
+                        let closest = closest_cursor_index_and_xy(abs_xy, shown_text(&text, &masked), infos, font);
+
+                        // If the press lands within the current selection, begin moving it.
+                        let within_selection = match (closest, selected_char_range(cursor, infos)) {
+                            (Some((closest_cursor, _)), Some(selected)) => {
+                                let line_infos = infos.iter().cloned();
+                                text::glyph::index_after_cursor(line_infos, closest_cursor)
+                                    .map(|idx| selected.start < idx && idx < selected.end)
+                                    .unwrap_or(false)
+                            },
+                            _ => false,
+                        };
+
+                        if within_selection {
+                            drag = Some(Drag::MoveSelection { drop: None });
+                        } else {
+                            if let Some((closest_cursor, _)) = closest {
+                                cursor = Cursor::Idx(closest_cursor);
+                            }
+                            drag = Some(Drag::Selecting);
                         }
-
-                        // TODO: Differentiate between Selecting and MoveSelection.
-                        drag = Some(Drag::Selecting);
                     }
 
                     // Check for control keys.
                     event::Button::Keyboard(key) => match key {
 
-                        // If `Cursor::Idx`, remove the `char` behind the cursor.
+                        // If `Cursor::Idx`, remove the grapheme cluster behind the cursor.
                         // If `Cursor::Selection`, remove the selected text.
                         input::Key::Backspace | input::Key::Delete => {
                             let delete_word = press.modifiers.contains(input::keyboard::ModifierKey::CTRL);
@@ -467,16 +876,16 @@ impl<'a> Widget for TextEdit<'a> {
 
                                     let end = match (key, delete_word) {
                                         (input::Key::Backspace, false) => {
-                                            cursor_idx.previous(line_infos)
+                                            cursor_idx.previous_grapheme(shown_text(&text, &masked), line_infos)
                                         }
                                         (input::Key::Backspace, true) => {
-                                            cursor_idx.previous_word_start(&text, line_infos)
+                                            cursor_idx.previous_word_start(shown_text(&text, &masked), line_infos)
                                         }
                                         (input::Key::Delete, false) => {
-                                            cursor_idx.next(line_infos)
+                                            cursor_idx.next_grapheme(shown_text(&text, &masked), line_infos)
                                         }
                                         (input::Key::Delete, true) => {
-                                            cursor_idx.next_word_end(&text, line_infos)
+                                            cursor_idx.next_word_end(shown_text(&text, &masked), line_infos)
                                         }
                                         _ => unreachable!(),
                                     }.unwrap_or(cursor_idx);
@@ -504,16 +913,21 @@ impl<'a> Widget for TextEdit<'a> {
                                                                     new_cursor_char_idx)
                                         .expect("char index was out of range")
                                 };
-                                cursor = Cursor::Idx(new_cursor_idx);
+                                let new_cursor = Cursor::Idx(new_cursor_idx);
+                                let edit = Edit::new(&text, start_idx..end_idx, "", cursor, new_cursor);
+                                events.push(edit.event());
+                                cursor = new_cursor;
                                 *text.to_mut() = text.chars().take(start_idx)
                                     .chain(text.chars().skip(end_idx))
                                     .collect();
+                                masked = masked_text(&text, mask);
                                 state.update(|state| {
                                     let font = ui.fonts.get(font_id).unwrap();
                                     let w = rect.w();
                                     state.line_infos =
-                                        line_infos(&text, font, font_size, line_wrap, w)
+                                        line_infos(shown_text(&text, &masked), font, font_size, line_wrap, tab_stops, w)
                                             .collect();
+                                    state.history.push(edit, &text, false, undo_limit);
                                 });
                             }
                         },
@@ -532,16 +946,16 @@ impl<'a> Widget for TextEdit<'a> {
                                 let line_infos = state.line_infos.iter().cloned();
                                 match (key, move_word) {
                                     (input::Key::Left, true) => cursor_idx
-                                        .previous_word_start(&text, line_infos),
+                                        .previous_word_start(shown_text(&text, &masked), line_infos),
                                     (input::Key::Right, true) => cursor_idx
-                                        .next_word_end(&text, line_infos),
+                                        .next_word_end(shown_text(&text, &masked), line_infos),
                                     (input::Key::Left, false) => cursor_idx
-                                        .previous(line_infos),
+                                        .previous_grapheme(shown_text(&text, &masked), line_infos),
                                     (input::Key::Right, false) => cursor_idx
-                                        .next(line_infos),
+                                        .next_grapheme(shown_text(&text, &masked), line_infos),
 
                                     // Up/Down movement
-                                    _ => cursor_xy_at(cursor_idx, &text, &state.line_infos, font)
+                                    _ => cursor_xy_at(cursor_idx, shown_text(&text, &masked), &state.line_infos, font)
                                         .and_then(|(x_pos, _)| {
                                             let text::cursor::Index { line, .. } = cursor_idx;
                                             let next_line = match key {
@@ -549,7 +963,7 @@ impl<'a> Widget for TextEdit<'a> {
                                                 input::Key::Down => line + 1,
                                                 _ => unreachable!(),
                                             };
-                                            closest_cursor_index_on_line(x_pos, next_line, &text, &state.line_infos, font)
+                                            closest_cursor_index_on_line(x_pos, next_line, shown_text(&text, &masked), &state.line_infos, font)
                                         })
                                 }.unwrap_or(cursor_idx)
                             };
@@ -583,10 +997,10 @@ impl<'a> Widget for TextEdit<'a> {
                                                 let line_infos = state.line_infos.iter().cloned();
                                                 match key {
                                                     input::Key::Left | input::Key::Up => {
-                                                        cursor_idx.previous_word_start(&text, line_infos)
+                                                        cursor_idx.previous_word_start(shown_text(&text, &masked), line_infos)
                                                     },
                                                     input::Key::Right | input::Key::Down => {
-                                                        cursor_idx.next_word_end(&text, line_infos)
+                                                        cursor_idx.next_word_end(shown_text(&text, &masked), line_infos)
                                                     }
                                                     _ => unreachable!(),
                                                 }.unwrap_or(cursor_idx)
@@ -653,13 +1067,162 @@ impl<'a> Widget for TextEdit<'a> {
                             }
                         },
 
+                        // Undo on Ctrl+Z, redo on Ctrl+Shift+Z or Ctrl+Y.
+                        input::Key::Z | input::Key::Y => {
+                            if !press.modifiers.contains(input::keyboard::ModifierKey::CTRL) {
+                                continue 'events;
+                            }
+                            let shift = press.modifiers.contains(input::keyboard::ModifierKey::SHIFT);
+                            let is_undo = key == input::Key::Z && !shift;
+                            let mut history = state.history.clone();
+                            let restored = if is_undo { history.undo(&text) } else { history.redo(&text) };
+                            if let Some((new_text, new_cursor)) = restored {
+                                // The restored edit is now the last on the opposite stack.
+                                let event = if is_undo {
+                                    history.redo.last().map(|edit| edit.inverse().event())
+                                } else {
+                                    history.undo.last().map(Edit::event)
+                                };
+                                events.extend(event);
+                                *text.to_mut() = new_text;
+                                masked = masked_text(&text, mask);
+                                let font = ui.fonts.get(font_id).unwrap();
+                                let new_line_infos: Vec<_> =
+                                    line_infos(shown_text(&text, &masked), font, font_size, line_wrap, tab_stops, rect.w())
+                                        .collect();
+                                cursor = match new_cursor {
+                                    Cursor::Idx(idx) =>
+                                        Cursor::Idx(idx.clamp_to_lines(new_line_infos.iter().cloned())),
+                                    Cursor::Selection { start, end } => Cursor::Selection {
+                                        start: start.clamp_to_lines(new_line_infos.iter().cloned()),
+                                        end: end.clamp_to_lines(new_line_infos.iter().cloned()),
+                                    },
+                                };
+                                state.update(|state| {
+                                    state.line_infos = new_line_infos.into();
+                                    state.history = history;
+                                });
+                            }
+                        },
+
+                        // Copy on Ctrl+C, cut on Ctrl+X and paste on Ctrl+V (Cmd on macOS).
+                        input::Key::C | input::Key::X | input::Key::V => {
+                            let ctrl = press.modifiers.contains(input::keyboard::ModifierKey::CTRL);
+                            let gui = press.modifiers.contains(input::keyboard::ModifierKey::GUI);
+                            if !ctrl && !gui {
+                                continue 'events;
+                            }
+
+                            if key == input::Key::V {
+                                let contents = match ui.clipboard_contents() {
+                                    Some(ref contents) if !contents.is_empty() => contents.clone(),
+                                    _ => continue 'events,
+                                };
+                                let font = ui.fonts.get(font_id).unwrap();
+                                match insert_text(&contents, cursor, &text, &state.line_infos, font) {
+                                    Some((new_text, new_masked, new_cursor, new_line_infos, replaced, inserted)) => {
+                                        let edit = Edit::new(&text, replaced, &inserted, cursor, new_cursor);
+                                        events.push(edit.event());
+                                        *text.to_mut() = new_text;
+                                        masked = new_masked;
+                                        cursor = new_cursor;
+                                        state.update(|state| {
+                                            state.line_infos = new_line_infos.into();
+                                            state.history.push(edit, &text, false, undo_limit);
+                                        });
+                                    }, _ => ()
+                                }
+                                continue 'events;
+                            }
+
+                            // Masked text may not be copied out of the `TextEdit`.
+                            if mask.is_some() {
+                                continue 'events;
+                            }
+
+                            let (start_idx, end_idx) = match selected_char_range(cursor, &state.line_infos) {
+                                Some(range) => (range.start, range.end),
+                                None => continue 'events,
+                            };
+                            let selected = text.chars().skip(start_idx).take(end_idx - start_idx).collect();
+                            ui.set_clipboard_contents(selected);
+
+                            if key == input::Key::X {
+                                let new_cursor_idx = {
+                                    let line_infos = state.line_infos.iter().cloned();
+                                    text::cursor::index_before_char(line_infos, start_idx)
+                                        .expect("char index was out of range")
+                                };
+                                let new_cursor = Cursor::Idx(new_cursor_idx);
+                                let edit = Edit::new(&text, start_idx..end_idx, "", cursor, new_cursor);
+                                events.push(edit.event());
+                                cursor = new_cursor;
+                                *text.to_mut() = text.chars().take(start_idx)
+                                    .chain(text.chars().skip(end_idx))
+                                    .collect();
+                                masked = masked_text(&text, mask);
+                                state.update(|state| {
+                                    let font = ui.fonts.get(font_id).unwrap();
+                                    let w = rect.w();
+                                    state.line_infos =
+                                        line_infos(shown_text(&text, &masked), font, font_size, line_wrap, tab_stops, w)
+                                            .collect();
+                                    state.history.push(edit, &text, false, undo_limit);
+                                });
+                            }
+                        },
+
                         input::Key::Return => {
+                            if single_line {
+                                events.push(Event::Submit);
+                                continue 'events;
+                            }
+
+                            // Begin the new line with the indentation of the current line.
+                            let mut newline = "\n".to_string();
+                            if style.auto_indent(ui.theme()) {
+                                if let Some(range) = cursor_char_range(cursor, &state.line_infos) {
+                                    newline.push_str(indentation_before(&text, range.start));
+                                }
+                            }
+                            let font = ui.fonts.get(font_id).unwrap();
+                            match insert_text(&newline, cursor, &text, &state.line_infos, font) {
+                                Some((new_text, new_masked, new_cursor, new_line_infos, replaced, inserted)) => {
+                                    let edit = Edit::new(&text, replaced, &inserted, cursor, new_cursor);
+                                    events.push(edit.event());
+                                    *text.to_mut() = new_text;
+                                    masked = new_masked;
+                                    cursor = new_cursor;
+                                    state.update(|state| {
+                                        state.line_infos = new_line_infos.into();
+                                        state.history.push(edit, &text, false, undo_limit);
+                                    });
+                                }, _ => ()
+                            }
+                        },
+
+                        // Insert a tab rather than moving the keyboard focus to the next widget,
+                        // unless the text is restricted to a single line. `Shift+Tab` and
+                        // `Ctrl+Tab` still move the focus so that it cannot become trapped.
+                        input::Key::Tab => {
+                            if single_line
+                            || press.modifiers.contains(input::keyboard::ModifierKey::SHIFT)
+                            || press.modifiers.contains(input::keyboard::ModifierKey::CTRL) {
+                                continue 'events;
+                            }
+                            consumed_tab = true;
                             let font = ui.fonts.get(font_id).unwrap();
-                            match insert_text("\n", cursor, &text, &state.line_infos, font) {
-                                Some((new_text, new_cursor, new_line_infos)) => {
+                            match insert_text("\t", cursor, &text, &state.line_infos, font) {
+                                Some((new_text, new_masked, new_cursor, new_line_infos, replaced, inserted)) => {
+                                    let edit = Edit::new(&text, replaced, &inserted, cursor, new_cursor);
+                                    events.push(edit.event());
                                     *text.to_mut() = new_text;
+                                    masked = new_masked;
                                     cursor = new_cursor;
-                                    state.update(|state| state.line_infos = new_line_infos);
+                                    state.update(|state| {
+                                        state.line_infos = new_line_infos.into();
+                                        state.history.push(edit, &text, true, undo_limit);
+                                    });
                                 }, _ => ()
                             }
                         },
@@ -674,12 +1237,54 @@ impl<'a> Widget for TextEdit<'a> {
                 event::Widget::Release(release) => {
                     // Release drag.
                     if let event::Button::Mouse(input::MouseButton::Left, _) = release.button {
+                        // If selected text was being dragged, drop it at the new position.
+                        if let Some(Drag::MoveSelection { drop }) = drag {
+                            let copy = release.modifiers.contains(input::keyboard::ModifierKey::CTRL);
+                            let font = ui.fonts.get(font_id).unwrap();
+                            let moved = match drop {
+                                Some(drop) => move_selection(cursor, drop, copy, &text, &state.line_infos)
+                                    .filter(|&(ref new_text, ..)| {
+                                        max_length.map_or(true, |max| new_text.chars().count() <= max)
+                                    })
+                                    .map(|(new_text, replaced, inserted, new_selection)| {
+                                        let new_masked = masked_text(&new_text, mask);
+                                        let new_line_infos: Vec<_> =
+                                            line_infos(shown_text(&new_text, &new_masked), font, font_size, line_wrap, tab_stops, rect.w())
+                                                .collect();
+                                        let line_infos = new_line_infos.iter().cloned();
+                                        let start = text::cursor::index_before_char(line_infos.clone(), new_selection.start);
+                                        let end = text::cursor::index_before_char(line_infos, new_selection.end);
+                                        (new_text, new_masked, replaced, inserted, start, end, new_line_infos)
+                                    }),
+                                None => None,
+                            };
+                            match moved {
+                                Some((new_text, new_masked, replaced, inserted, Some(start), Some(end), new_line_infos)) => {
+                                    let new_cursor = Cursor::Selection { start: start, end: end };
+                                    let edit = Edit::new(&text, replaced, &inserted, cursor, new_cursor);
+                                    events.push(edit.event());
+                                    *text.to_mut() = new_text;
+                                    masked = new_masked;
+                                    cursor = new_cursor;
+                                    state.update(|state| {
+                                        state.line_infos = new_line_infos.into();
+                                        state.history.push(edit, &text, false, undo_limit);
+                                    });
+                                },
+                                // If the selection was clicked without being moved elsewhere,
+                                // place the cursor at the drop position.
+                                _ => if let Some(drop) = drop {
+                                    cursor = Cursor::Idx(drop);
+                                },
+                            }
+                        }
                         drag = None;
                     }
                 },
 
                 event::Widget::Text(event::Text { string, modifiers }) => {
                     if modifiers.contains(input::keyboard::ModifierKey::CTRL)
+                    || modifiers.contains(input::keyboard::ModifierKey::GUI)
                     || string.chars().count() == 0
                     || string.chars().next().is_none() {
                         continue 'events;
@@ -696,16 +1301,89 @@ impl<'a> Widget for TextEdit<'a> {
                         _ => ()
                     }
 
+                    // Tabs are inserted upon `Tab` presses, as some backends also produce text
+                    // for them while others do not.
+                    if string == "\t" {
+                        continue 'events;
+                    }
+
                     let font = ui.fonts.get(font_id).unwrap();
                     match insert_text(&string, cursor, &text, &state.line_infos, font) {
-                        Some((new_text, new_cursor, new_line_infos)) => {
+                        Some((new_text, new_masked, new_cursor, new_line_infos, replaced, inserted)) => {
+                            let edit = Edit::new(&text, replaced, &inserted, cursor, new_cursor);
+                            events.push(edit.event());
                             *text.to_mut() = new_text;
+                            masked = new_masked;
                             cursor = new_cursor;
-                            state.update(|state| state.line_infos = new_line_infos);
+                            state.update(|state| {
+                                state.line_infos = new_line_infos.into();
+                                state.history.push(edit, &text, true, undo_limit);
+                            });
                         }, _ => ()
                     }
                 },
 
+                // Track the pre-edit text of IME compositions, inserting the text once committed.
+                event::Widget::Ime(ime) => match ime {
+                    event::Ime::Preedit(mut preedit) => {
+                        // The IME's cursor is given by the backend and may not lie within the text
+                        // or on `char` boundaries, in which case it is discarded.
+                        preedit.cursor = match preedit.cursor {
+                            Some(ref range) => {
+                                let end = std::cmp::min(range.end, preedit.text.len());
+                                let start = std::cmp::min(range.start, end);
+                                let is_valid = preedit.text.is_char_boundary(start)
+                                    && preedit.text.is_char_boundary(end);
+                                if is_valid { Some(start..end) } else { None }
+                            },
+                            None => None,
+                        };
+                        let preedit = if preedit.text.is_empty() { None } else { Some(preedit) };
+                        if state.preedit != preedit {
+                            state.update(|state| state.preedit = preedit);
+                        }
+                    },
+                    event::Ime::Cancel => {
+                        if state.preedit.is_some() {
+                            state.update(|state| state.preedit = None);
+                        }
+                    },
+                    event::Ime::Commit(string) => {
+                        if state.preedit.is_some() {
+                            state.update(|state| state.preedit = None);
+                        }
+                        if string.is_empty() {
+                            continue 'events;
+                        }
+                        let font = ui.fonts.get(font_id).unwrap();
+                        match insert_text(&string, cursor, &text, &state.line_infos, font) {
+                            Some((new_text, new_masked, new_cursor, new_line_infos, replaced, inserted)) => {
+                                let edit = Edit::new(&text, replaced, &inserted, cursor, new_cursor);
+                                events.push(edit.event());
+                                *text.to_mut() = new_text;
+                                masked = new_masked;
+                                cursor = new_cursor;
+                                state.update(|state| {
+                                    state.line_infos = new_line_infos.into();
+                                    state.history.push(edit, &text, true, undo_limit);
+                                });
+                            }, _ => ()
+                        }
+                    },
+                },
+
+                event::Widget::CapturesInputSource(input::Source::Keyboard) => {
+                    events.push(Event::FocusGained);
+                },
+
+                // Discard any composition in progress when the keyboard is no longer captured.
+                event::Widget::UncapturesInputSource(input::Source::Keyboard) => {
+                    if state.preedit.is_some() {
+                        state.update(|state| state.preedit = None);
+                    }
+                    events.push(Event::FocusLost);
+                },
+
                 // Check whether or not we need to extend a text selection or drag some text.
                 event::Widget::Drag(drag_event) if drag_event.button == input::MouseButton::Left => {
                     match drag {
@@ -718,7 +1396,7 @@ impl<'a> Widget for TextEdit<'a> {
                             let abs_xy = utils::vec2_add(drag_event.to, rect.xy());
                             let infos = &state.line_infos;
                             let font = ui.fonts.get(font_id).unwrap();
-                            match closest_cursor_index_and_xy(abs_xy, &text, infos, font) {
+                            match closest_cursor_index_and_xy(abs_xy, shown_text(&text, &masked), infos, font) {
                                 Some((end_cursor_idx, _)) =>
                                     cursor = Cursor::Selection {
                                         start: start_cursor_idx,
@@ -728,9 +1406,15 @@ impl<'a> Widget for TextEdit<'a> {
                             }
                         },
 
-                        // TODO: This should move the selected text.
-                        Some(Drag::MoveSelection) => {
-                            unimplemented!();
+                        // Track the position at which the selected text would be dropped.
+                        Some(Drag::MoveSelection { .. }) => {
+                            let abs_xy = utils::vec2_add(drag_event.to, rect.xy());
+                            let infos = &state.line_infos;
+                            let font = ui.fonts.get(font_id).unwrap();
+                            if let Some((drop_cursor_idx, _)) =
+                                closest_cursor_index_and_xy(abs_xy, shown_text(&text, &masked), infos, font) {
+                                drag = Some(Drag::MoveSelection { drop: Some(drop_cursor_idx) });
+                            }
                         },
 
                         None => (),
@@ -741,63 +1425,397 @@ impl<'a> Widget for TextEdit<'a> {
             }
         }
 
+        if consumed_tab {
+            ui.cancel_focus_traversal();
+        }
+
         if let Some(_) = ui.widget_input(id).mouse() {
-            ui.set_mouse_cursor(cursor::MouseCursor::Text);
+            match drag {
+                Some(Drag::MoveSelection { .. }) => ui.set_mouse_cursor(cursor::MouseCursor::Grabbing),
+                _ => ui.set_mouse_cursor(cursor::MouseCursor::Text),
+            }
+        }
+
+        // The matches of the search within the text, shared by the search action and the
+        // highlighting of the visible matches below.
+        let mut matches = match search {
+            Some(search) => search.matches(&text),
+            None => Vec::new(),
+        };
+
+        // Perform the requested action upon the matches of the search.
+        //
+        // Replacements are passed through the `filter` and restricted to a single line and to the
+        // `max_length` in the same manner as entered text.
+        if let (Some(search), Some(action)) = (search, search_action) {
+            let selected = cursor_char_range(cursor, &state.line_infos).unwrap_or(0..0);
+            let font = ui.fonts.get(font_id).unwrap();
+
+            // Replace the given range of chars with `inserted`, producing the new text, its
+            // `line::Info`s and the matches of the search within it, or `None` if the new text
+            // would exceed the height restriction.
+            let splice = |replaced: std::ops::Range<usize>, inserted: &str, text: &str| {
+                let new_text: String = text.chars().take(replaced.start)
+                    .chain(inserted.chars())
+                    .chain(text.chars().skip(replaced.end))
+                    .collect();
+                let new_line_infos: Vec<_> =
+                    line_infos(&new_text, font, font_size, line_wrap, tab_stops, rect.w()).collect();
+                let height = text::height(new_line_infos.len(), font_size, line_spacing);
+                if height < rect.h() || !restrict_to_height {
+                    let new_matches = search.matches(&new_text);
+                    Some((new_text, new_line_infos, new_matches))
+                } else {
+                    None
+                }
+            };
+
+            let replacement = match action {
+                SearchAction::Next => {
+                    cursor = next_match(&matches, selected.end)
+                        .and_then(|m| selection_cursor(m, &state.line_infos))
+                        .unwrap_or(cursor);
+                    None
+                },
+                SearchAction::Prev => {
+                    cursor = prev_match(&matches, selected.start)
+                        .and_then(|m| selection_cursor(m, &state.line_infos))
+                        .unwrap_or(cursor);
+                    None
+                },
+                SearchAction::Replace(replacement) => {
+                    if matches.contains(&selected) {
+                        filter_text(replacement)
+                            .map(|replacement| truncate_to_max_length(replacement, selected.len(), &text))
+                            .and_then(|replacement| {
+                                let spliced = splice(selected.clone(), &replacement, &text);
+                                spliced.map(|(new_text, new_line_infos, new_matches)| {
+                                    // Select the match following the replacement.
+                                    let end = selected.start + replacement.chars().count();
+                                    let new_cursor = next_match(&new_matches, end)
+                                        .and_then(|m| selection_cursor(m, &new_line_infos))
+                                        .or_else(|| {
+                                            let line_infos = new_line_infos.iter().cloned();
+                                            text::cursor::index_before_char(line_infos, end).map(Cursor::Idx)
+                                        })
+                                        .unwrap_or(cursor);
+                                    (new_text, new_line_infos, new_matches, selected.clone(), replacement, new_cursor)
+                                })
+                            })
+                    } else {
+                        cursor = next_match(&matches, selected.end)
+                            .and_then(|m| selection_cursor(m, &state.line_infos))
+                            .unwrap_or(cursor);
+                        None
+                    }
+                },
+                SearchAction::ReplaceAll(replacement) => {
+                    match (filter_text(replacement), matches.first(), matches.last()) {
+                        (Some(replacement), Some(first), Some(last)) => {
+                            // Replace the span from the first match to the last as a single edit.
+                            let chars: Vec<char> = text.chars().collect();
+                            let mut inserted = String::new();
+                            let mut prev_end = first.start;
+                            for m in &matches {
+                                inserted.extend(&chars[prev_end..m.start]);
+                                inserted.push_str(&replacement);
+                                prev_end = m.end;
+                            }
+                            let replaced = first.start..last.end;
+
+                            // Rather than truncating some of the replacements, none are made if
+                            // the new text would exceed the `max_length`.
+                            let new_len = chars.len() - replaced.len() + inserted.chars().count();
+                            if max_length.map_or(false, |max_length| new_len > max_length) {
+                                None
+                            } else {
+                                let spliced = splice(replaced.clone(), &inserted, &text);
+                                spliced.map(|(new_text, new_line_infos, new_matches)| {
+                                    let end = first.start + inserted.chars().count();
+                                    let new_cursor = {
+                                        let line_infos = new_line_infos.iter().cloned();
+                                        text::cursor::index_before_char(line_infos, end)
+                                            .map(Cursor::Idx)
+                                            .unwrap_or(cursor)
+                                    };
+                                    (new_text, new_line_infos, new_matches, replaced, inserted, new_cursor)
+                                })
+                            }
+                        },
+                        _ => None,
+                    }
+                },
+            };
+
+            if let Some((new_text, new_line_infos, new_matches, replaced, inserted, new_cursor)) = replacement {
+                let edit = Edit::new(&text, replaced, &inserted, cursor, new_cursor);
+                events.push(edit.event());
+                *text.to_mut() = new_text;
+                masked = masked_text(&text, mask);
+                matches = new_matches;
+                cursor = new_cursor;
+                state.update(|state| {
+                    state.line_infos = new_line_infos.into();
+                    state.history.push(edit, &text, false, undo_limit);
+                });
+            }
         }
 
         let cursor_has_changed = state.cursor != cursor;
         if cursor_has_changed {
             state.update(|state| state.cursor = cursor);
+            let line_infos = state.line_infos.iter().cloned();
+            let char_idx = |idx| text::glyph::index_after_cursor(line_infos.clone(), idx);
+            let event = match cursor {
+                Cursor::Idx(idx) => char_idx(idx).map(Event::CursorMoved),
+                Cursor::Selection { start, end } => match (char_idx(start), char_idx(end)) {
+                    (Some(start), Some(end)) => Some(Event::SelectionChanged { start: start, end: end }),
+                    _ => None,
+                },
+            };
+            events.extend(event);
         }
 
         if state.drag != drag {
             state.update(|state| state.drag = drag);
         }
 
-        // Takes the `String` from the `Cow` if the `Cow` is `Owned`.
-        fn take_if_owned(text: std::borrow::Cow<str>) -> Option<String> {
-            match text {
-                std::borrow::Cow::Borrowed(_) => None,
-                std::borrow::Cow::Owned(s) => Some(s),
+        // Produces an `Update` with the `String` from the `Cow` if the `Cow` is `Owned`.
+        fn with_update(text: std::borrow::Cow<str>, mut events: Vec<Event>) -> Vec<Event> {
+            if let std::borrow::Cow::Owned(s) = text {
+                events.push(Event::Update(s));
             }
+            events
         }
 
+        // While an IME composition is in progress, display its pre-edit text in place.
+        let composition = match state.preedit {
+            Some(ref preedit) => cursor_char_range(cursor, &state.line_infos).map(|replaced| {
+                let preedit_len = preedit.text.chars().count();
+                let caret = match preedit.cursor {
+                    Some(ref range) => preedit.text[..range.start].chars().count(),
+                    None => preedit_len,
+                };
+                let composed_text: String = text.chars().take(replaced.start)
+                    .chain(preedit.text.chars())
+                    .chain(text.chars().skip(replaced.end))
+                    .collect();
+                let composed_text = masked_text(&composed_text, mask).unwrap_or(composed_text);
+                let font = ui.fonts.get(font_id).unwrap();
+                let composed_line_infos =
+                    line_infos(&composed_text, font, font_size, line_wrap, tab_stops, rect.w())
+                        .collect();
+                Composition {
+                    text: composed_text,
+                    line_infos: composed_line_infos,
+                    preedit: replaced.start..replaced.start + preedit_len,
+                    caret: replaced.start + caret,
+                }
+            }),
+            None => None,
+        };
+        let shown_text = shown_text(&text, &masked);
+        let display_text = match composition {
+            Some(ref composition) => &composition.text[..],
+            None => shown_text,
+        };
+
         let color = style.color(ui.theme());
         let font_size = style.font_size(ui.theme());
-        let num_lines = state.line_infos.iter().count();
+        let num_lines = composition.as_ref().map_or(state.line_infos.len(), |c| c.line_infos.len());
         let text_height = text::height(num_lines, font_size, line_spacing);
         let text_y_range = Range::new(0.0, text_height).align_to(y_align, rect.y);
         let text_rect = Rect { x: rect.x, y: text_y_range };
 
-        match line_wrap {
-            Wrap::Whitespace => widget::Text::new(&text).wrap_by_word(),
-            Wrap::Character => widget::Text::new(&text).wrap_by_character(),
+        match highlight {
+
+            // Draw the visible lines as runs of differently colored text.
+            Some(highlight) => {
+                let runs: Vec<(std::ops::Range<usize>, Rect, Color)> = {
+                    let infos = match composition {
+                        Some(ref composition) => &composition.line_infos[..],
+                        None => &state.line_infos[..],
+                    };
+                    let font = ui.fonts.get(font_id).unwrap();
+                    let mut runs = Vec::new();
+                    if let Some(visible) = visible_rect(ui, id, rect) {
+                        // Skip the lines above the visible area without laying them out.
+                        let (first, line_rects) =
+                            text::line::rects_below(infos, font_size, text_rect, justify, y_align,
+                                                    line_spacing, visible.top());
+                        for (info, line_rect) in infos[first..].iter().zip(line_rects) {
+                            if line_rect.bottom() > visible.top() {
+                                continue;
+                            }
+                            if line_rect.top() < visible.bottom() {
+                                break;
+                            }
+                            let line = &display_text[info.byte_range()];
+                            for (range, run_color) in line_runs(line, highlight(line), color) {
+                                let x = line_rect.left() + text::line::width_with_tab_stops(
+                                    &line[..range.start], font, font_size, tab_stops);
+                                let w = text::line::width_with_tab_stops(
+                                    &line[range.clone()], font, font_size, tab_stops);
+                                let run_rect = Rect { x: Range::new(x, x + w), y: line_rect.y };
+                                let bytes = info.start_byte + range.start..info.start_byte + range.end;
+                                runs.push((bytes, run_rect, run_color));
+                            }
+                        }
+                    }
+                    runs
+                };
+
+                if state.ids.text_runs.len() < runs.len() {
+                    let num_runs = runs.len();
+                    let id_gen = &mut ui.widget_id_generator();
+                    state.update(|state| state.ids.text_runs.resize(num_runs, id_gen));
+                }
+
+                let iter = state.ids.text_runs.iter().zip(&runs);
+                for (&run_id, &(ref bytes, run_rect, run_color)) in iter {
+                    widget::Text::new(&display_text[bytes.clone()])
+                        .no_line_wrap()
+                        .left_justify()
+                        .font_id(font_id)
+                        .wh(run_rect.dim())
+                        .xy(run_rect.xy())
+                        .parent(id)
+                        .graphics_for(id)
+                        .color(run_color)
+                        .font_size(font_size)
+                        .tab_stops(tab_stops)
+                        .set(run_id, ui);
+                }
+            },
+
+            None => {
+                match line_wrap {
+                    Wrap::Whitespace => widget::Text::new(display_text).wrap_by_word(),
+                    Wrap::Character => widget::Text::new(display_text).wrap_by_character(),
+                }
+                    .font_id(font_id)
+                    .wh(text_rect.dim())
+                    .xy(text_rect.xy())
+                    .justify(justify)
+                    .parent(id)
+                    .graphics_for(id)
+                    .color(color)
+                    .line_spacing(line_spacing)
+                    .font_size(font_size)
+                    .tab_stops(tab_stops)
+                    .set(state.ids.text, ui);
+            },
         }
-            .font_id(font_id)
-            .wh(text_rect.dim())
-            .xy(text_rect.xy())
-            .justify(justify)
-            .parent(id)
-            .graphics_for(id)
-            .color(color)
-            .line_spacing(line_spacing)
-            .font_size(font_size)
-            .set(state.ids.text, ui);
-
-        // Draw the line for the cursor.
-        let cursor_idx = match cursor {
-            Cursor::Idx(idx) => idx,
-            Cursor::Selection { end, .. } => end,
+
+        // Hint at the expected content while the text is empty.
+        if let (Some(placeholder), true) = (placeholder, display_text.is_empty()) {
+            let num_lines = {
+                let font = ui.fonts.get(font_id).unwrap();
+                line_infos(placeholder, font, font_size, line_wrap, tab_stops, rect.w()).count()
+            };
+            let placeholder_height = text::height(num_lines, font_size, line_spacing);
+            let placeholder_y_range = Range::new(0.0, placeholder_height).align_to(y_align, rect.y);
+            let placeholder_rect = Rect { x: rect.x, y: placeholder_y_range };
+            let placeholder_color = style.placeholder_color(ui.theme());
+            match line_wrap {
+                Wrap::Whitespace => widget::Text::new(placeholder).wrap_by_word(),
+                Wrap::Character => widget::Text::new(placeholder).wrap_by_character(),
+            }
+                .font_id(font_id)
+                .wh(placeholder_rect.dim())
+                .xy(placeholder_rect.xy())
+                .justify(justify)
+                .parent(id)
+                .graphics_for(id)
+                .color(placeholder_color)
+                .line_spacing(line_spacing)
+                .font_size(font_size)
+                .tab_stops(tab_stops)
+                .set(state.ids.placeholder, ui);
+        }
+
+        // Highlight the visible matches of the search, unless a composition is in progress.
+        if search.is_some() && composition.is_none() {
+            let match_rects: Vec<Rect> = {
+                let mut match_rects = Vec::new();
+                if let (false, Some(visible)) = (matches.is_empty(), visible_rect(ui, id, rect)) {
+                    let (first, line_rects) =
+                        text::line::rects_below(&state.line_infos, font_size, rect, justify,
+                                                y_align, line_spacing, visible.top());
+                    let infos = state.line_infos[first..].iter().cloned();
+                    let font = ui.fonts.get(font_id).unwrap();
+                    let mut matches = matches.iter().peekable();
+                    for (info, line_rect) in infos.zip(line_rects) {
+                        if line_rect.bottom() > visible.top() {
+                            continue;
+                        }
+                        if line_rect.top() < visible.bottom() {
+                            break;
+                        }
+                        // Skip the matches that end before this line.
+                        let chars = info.char_range();
+                        while matches.peek().map_or(false, |m| m.end <= chars.start) {
+                            matches.next();
+                        }
+                        let line = &text[info.byte_range()];
+                        for m in matches.clone().take_while(|m| m.start < chars.end) {
+                            let start = std::cmp::max(m.start, chars.start) - chars.start;
+                            let end = std::cmp::min(m.end, chars.end) - chars.start;
+                            let start = text::cursor::Index { line: 0, char: start };
+                            let end = text::cursor::Index { line: 0, char: end };
+                            let line_with_rect = std::iter::once((line, line_rect));
+                            let rects = text::line::selected_rects(line_with_rect, font, font_size, start, end)
+                                .tab_stops(tab_stops);
+                            match_rects.extend(rects);
+                        }
+                    }
+                }
+                match_rects
+            };
+
+            if state.ids.search_matches.len() < match_rects.len() {
+                let num_rects = match_rects.len();
+                let id_gen = &mut ui.widget_id_generator();
+                state.update(|state| state.ids.search_matches.resize(num_rects, id_gen));
+            }
+
+            let search_match_color = style.search_match_color(ui.theme());
+            let iter = state.ids.search_matches.iter().zip(&match_rects);
+            for (&search_match_id, match_rect) in iter {
+                widget::Rectangle::fill(match_rect.dim())
+                    .xy(match_rect.xy())
+                    .color(search_match_color)
+                    .graphics_for(id)
+                    .parent(id)
+                    .set(search_match_id, ui);
+            }
+        }
+
+        // Draw the line for the cursor, or for the IME's caret while composing.
+        let cursor_idx = match composition {
+            Some(ref composition) => {
+                let line_infos = composition.line_infos.iter().cloned();
+                text::cursor::index_before_char(line_infos, composition.caret)
+                    .unwrap_or(text::cursor::Index { line: 0, char: 0 })
+            },
+            None => match cursor {
+                Cursor::Idx(idx) => idx,
+                Cursor::Selection { end, .. } => end,
+            },
         };
 
         // If this widget is not capturing the keyboard, no need to draw cursor or selection.
         if ui.global_input().current.widget_capturing_keyboard != Some(id) {
-            return take_if_owned(text);
+            return with_update(text, events);
         }
 
         let (cursor_x, cursor_y_range) = {
             let font = ui.fonts.get(font_id).unwrap();
-            cursor_xy_at(cursor_idx, &text, &state.line_infos, font)
+            let display_line_infos = match composition {
+                Some(ref composition) => &composition.line_infos[..],
+                None => &state.line_infos[..],
+            };
+            cursor_xy_at(cursor_idx, display_text, display_line_infos, font)
                 .unwrap_or_else(|| {
                     let x = rect.left();
                     let y = Range::new(0.0, font_size as Scalar).align_to(y_align, rect.y);
@@ -815,6 +1833,25 @@ impl<'a> Widget for TextEdit<'a> {
             .color(color)
             .set(state.ids.cursor, ui);
 
+        // Let the backend know where to position the IME's candidate window.
+        let cursor_rect = Rect { x: Range::new(cursor_x, cursor_x), y: cursor_y_range };
+        ui.set_ime_cursor_rect(Some(cursor_rect));
+
+        // While dragging selected text, indicate the position at which it would be dropped.
+        if let Some(Drag::MoveSelection { drop: Some(drop) }) = drag {
+            let font = ui.fonts.get(font_id).unwrap();
+            if let Some((drop_x, drop_y_range)) = cursor_xy_at(drop, shown_text, &state.line_infos, font) {
+                let start = [0.0, drop_y_range.start];
+                let end = [0.0, drop_y_range.end];
+                widget::Line::centred(start, end)
+                    .x_y(drop_x, drop_y_range.middle())
+                    .graphics_for(id)
+                    .parent(id)
+                    .color(color.alpha(0.5))
+                    .set(state.ids.drop_cursor, ui);
+            }
+        }
+
         // If the cursor position has changed due to input AND one of our parent widgets are
         // scrollable AND the change in cursor position would cause the cursor to fall outside the
         // scrollable parent's `Rect`, attempt to scroll the scrollable parent so that the cursor
@@ -840,17 +1877,61 @@ impl<'a> Widget for TextEdit<'a> {
             }
         }
 
+        // Underline the pre-edit text of the composition in progress.
+        if let Some(composition) = composition {
+            let underline_rects: Vec<Rect> = {
+                let line_infos = composition.line_infos.iter().cloned();
+                let start = text::cursor::index_before_char(line_infos.clone(), composition.preedit.start);
+                let end = text::cursor::index_before_char(line_infos.clone(), composition.preedit.end);
+                match (start, end) {
+                    (Some(start), Some(end)) => {
+                        let lines = line_infos.clone().map(|info| &composition.text[info.byte_range()]);
+                        let line_rects = text::line::rects(line_infos.clone(), font_size, rect,
+                                                           justify, y_align, line_spacing);
+                        let lines_with_rects = lines.zip(line_rects.clone());
+                        let font = ui.fonts.get(font_id).unwrap();
+                        let thickness = (font_size as Scalar / 16.0).max(1.0);
+                        text::line::selected_rects(lines_with_rects, font, font_size, start, end)
+                            .tab_stops(tab_stops)
+                            .map(|r| Rect { x: r.x, y: Range::new(r.bottom(), r.bottom() + thickness) })
+                            .collect()
+                    },
+                    _ => Vec::new(),
+                }
+            };
+
+            if state.ids.preedit_underlines.len() < underline_rects.len() {
+                let num_rects = underline_rects.len();
+                let id_gen = &mut ui.widget_id_generator();
+                state.update(|state| state.ids.preedit_underlines.resize(num_rects, id_gen));
+            }
+
+            let iter = state.ids.preedit_underlines.iter().zip(&underline_rects);
+            for (&underline_id, underline_rect) in iter {
+                widget::Rectangle::fill(underline_rect.dim())
+                    .xy(underline_rect.xy())
+                    .color(color)
+                    .graphics_for(id)
+                    .parent(id)
+                    .set(underline_id, ui);
+            }
+
+            return with_update(text, events);
+        }
+
         if let Cursor::Selection { start, end } = cursor {
             let (start, end) = (std::cmp::min(start, end), std::cmp::max(start, end));
 
             let selected_rects: Vec<Rect> = {
                 let line_infos = state.line_infos.iter().cloned();
-                let lines = line_infos.clone().map(|info| &text[info.byte_range()]);
+                let lines = line_infos.clone().map(|info| &shown_text[info.byte_range()]);
                 let line_rects = text::line::rects(line_infos.clone(), font_size, rect,
                                                    justify, y_align, line_spacing);
                 let lines_with_rects = lines.zip(line_rects.clone());
                 let font = ui.fonts.get(font_id).unwrap();
-                text::line::selected_rects(lines_with_rects, font, font_size, start, end).collect()
+                text::line::selected_rects(lines_with_rects, font, font_size, start, end)
+                    .tab_stops(tab_stops)
+                    .collect()
             };
 
             // Ensure we have at least as many widgets as selected_rectangles.
@@ -873,9 +1954,437 @@ impl<'a> Widget for TextEdit<'a> {
             }
         }
 
-        take_if_owned(text)
+        with_update(text, events)
+    }
+
+}
+
+
+impl<'a> Search<'a> {
+
+    /// A case-insensitive search for the given `query`, matching anywhere within words.
+    pub fn new(query: &'a str) -> Self {
+        Search {
+            query: query,
+            case_sensitive: false,
+            whole_word: false,
+        }
+    }
+
+    /// Specify whether or not matches must have the same case as the `query`.
+    pub fn case_sensitive(mut self, case_sensitive: bool) -> Self {
+        self.case_sensitive = case_sensitive;
+        self
+    }
+
+    /// Specify whether or not matches must be whole words.
+    pub fn whole_word(mut self, whole_word: bool) -> Self {
+        self.whole_word = whole_word;
+        self
+    }
+
+    /// The ranges of chars covered by each non-overlapping match of the `query` within the given
+    /// `text`, in order.
+    pub fn matches(&self, text: &str) -> Vec<std::ops::Range<usize>> {
+        let mut matches = Vec::new();
+        let query: Vec<char> = self.query.chars().collect();
+        if query.is_empty() {
+            return matches;
+        }
+        let chars: Vec<char> = text.chars().collect();
+        let is_word_char = |ch: char| ch.is_alphanumeric() || ch == '_';
+        let mut start = 0;
+        while start + query.len() <= chars.len() {
+            let end = start + query.len();
+            let is_match = chars[start..end].iter().zip(&query).all(|(&a, &b)| {
+                a == b || !self.case_sensitive && a.to_lowercase().eq(b.to_lowercase())
+            });
+            let is_whole_word = !self.whole_word
+                || (start == 0 || !is_word_char(chars[start - 1]))
+                && (end == chars.len() || !is_word_char(chars[end]));
+            if is_match && is_whole_word {
+                matches.push(start..end);
+                start = end;
+            } else {
+                start += 1;
+            }
+        }
+        matches
+    }
+
+}
+
+impl<'a> Filter<'a> {
+
+    /// Produce the text to insert in place of the given entered text, or `None` if it is rejected.
+    pub fn apply(&self, string: &str) -> Option<String> {
+        let filtered = match *self {
+            Filter::Numeric => string.chars().filter(|ch| ch.is_digit(10)).collect(),
+            Filter::Chars(accept) => string.chars().filter(|&ch| accept(ch)).collect(),
+            Filter::Custom(f) => match f(string) {
+                Some(filtered) => filtered,
+                None => return None,
+            },
+        };
+        if filtered.is_empty() { None } else { Some(filtered) }
+    }
+
+}
+
+impl Edit {
+
+    /// Describes replacing the given range of chars within `text` with `inserted`.
+    fn new(text: &str,
+           replaced: std::ops::Range<usize>,
+           inserted: &str,
+           cursor_before: Cursor,
+           cursor_after: Cursor) -> Self
+    {
+        let removed = text.chars().skip(replaced.start).take(replaced.len()).collect();
+        Edit {
+            start: replaced.start,
+            removed: removed,
+            inserted: inserted.to_string(),
+            cursor_before: cursor_before,
+            cursor_after: cursor_after,
+        }
+    }
+
+    /// Replace the range of `len` chars at `self.start` within `text` with `with`.
+    fn splice(&self, text: &str, len: usize, with: &str) -> String {
+        text.chars().take(self.start)
+            .chain(with.chars())
+            .chain(text.chars().skip(self.start + len))
+            .collect()
+    }
+
+    /// The `Event` describing this edit.
+    fn event(&self) -> Event {
+        let range = self.start..self.start + self.removed.chars().count();
+        match (self.removed.is_empty(), self.inserted.is_empty()) {
+            (true, _) => Event::Insert { at: self.start, text: self.inserted.clone() },
+            (false, true) => Event::Delete { range: range },
+            (false, false) => Event::Replace { range: range, text: self.inserted.clone() },
+        }
+    }
+
+    /// The edit that reverts this edit.
+    fn inverse(&self) -> Edit {
+        Edit {
+            start: self.start,
+            removed: self.inserted.clone(),
+            inserted: self.removed.clone(),
+            cursor_before: self.cursor_after,
+            cursor_after: self.cursor_before,
+        }
+    }
+
+}
+
+impl History {
+
+    /// The edits that may be undone, from oldest to most recent.
+    pub fn undo_edits(&self) -> &[Edit] {
+        &self.undo
+    }
+
+    /// The edits that may be redone, from the most recently undone to the oldest.
+    pub fn redo_edits(&self) -> &[Edit] {
+        &self.redo
+    }
+
+    /// Whether or not there is an edit that may be undone.
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    /// Whether or not there is an edit that may be redone.
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Whether or not the history describes changes that resulted in the given `text`.
+    fn applies_to(&self, text: &str) -> bool {
+        !self.can_undo() && !self.can_redo() || self.text_hash == hash_text(text)
+    }
+
+    /// Record the given `edit` which resulted in the given `text`.
+    ///
+    /// When `typing` is `true`, the edit is merged with the previous one if it continues the same
+    /// word (along with the whitespace that follows the word).
+    fn push(&mut self, edit: Edit, text: &str, typing: bool, limit: usize) {
+        if edit.removed.is_empty() && edit.inserted.is_empty() {
+            return;
+        }
+        self.redo.clear();
+        self.text_hash = hash_text(text);
+
+        let coalesce = self.coalesce && typing && edit.removed.is_empty();
+        let merged = match self.undo.last_mut() {
+            Some(last) if coalesce => {
+                let last_end = last.start + last.inserted.chars().count();
+                let ends_word = last.inserted.chars().last().map_or(false, char::is_whitespace)
+                    && !edit.inserted.chars().next().map_or(false, char::is_whitespace);
+                if last_end == edit.start && !ends_word {
+                    last.inserted.push_str(&edit.inserted);
+                    last.cursor_after = edit.cursor_after;
+                    true
+                } else {
+                    false
+                }
+            },
+            _ => false,
+        };
+
+        if !merged {
+            self.undo.push(edit);
+            if self.undo.len() > limit {
+                let excess = self.undo.len() - limit;
+                self.undo.drain(..excess);
+            }
+        }
+        self.coalesce = typing;
+    }
+
+    /// Undo the most recent edit to the given `text`.
+    ///
+    /// Returns the resulting text along with the cursor from before the edit.
+    fn undo(&mut self, text: &str) -> Option<(String, Cursor)> {
+        self.undo.pop().map(|edit| {
+            let new_text = edit.splice(text, edit.inserted.chars().count(), &edit.removed);
+            let cursor = edit.cursor_before;
+            self.text_hash = hash_text(&new_text);
+            self.coalesce = false;
+            self.redo.push(edit);
+            (new_text, cursor)
+        })
+    }
+
+    /// Redo the most recently undone edit to the given `text`.
+    ///
+    /// Returns the resulting text along with the cursor from after the edit.
+    fn redo(&mut self, text: &str) -> Option<(String, Cursor)> {
+        self.redo.pop().map(|edit| {
+            let new_text = edit.splice(text, edit.removed.chars().count(), &edit.inserted);
+            let cursor = edit.cursor_after;
+            self.text_hash = hash_text(&new_text);
+            self.coalesce = false;
+            self.undo.push(edit);
+            (new_text, cursor)
+        })
+    }
+
+}
+
+impl State {
+
+    /// The current position of the cursor or selection.
+    pub fn cursor(&self) -> Cursor {
+        self.cursor
+    }
+
+    /// Information about each line of the text as it is currently laid out.
+    pub fn line_infos(&self) -> &[text::line::Info] {
+        &self.line_infos
+    }
+
+    /// The edits that may be undone and redone.
+    ///
+    /// The `State` of a `TextEdit` may be retrieved via the `Ui`'s widget graph, e.g.
+    /// `ui.widget_graph().widget(id).and_then(|c| c.state_and_style::<State, Style>())`.
+    pub fn history(&self) -> &History {
+        &self.history
+    }
+
+}
+
+/// A hash used to check whether some text is the text produced by the most recent edit.
+fn hash_text(text: &str) -> u64 {
+    use std::hash::{Hash, Hasher};
+    let mut hasher = fnv::FnvHasher::default();
+    text.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// The whitespace at the beginning of the line containing the char at the given index, up to the
+/// index.
+fn indentation_before(text: &str, char_idx: usize) -> &str {
+    let byte_idx = text.char_indices().nth(char_idx).map_or(text.len(), |(i, _)| i);
+    let line_start = text[..byte_idx].rfind('\n').map_or(0, |i| i + 1);
+    let line = &text[line_start..byte_idx];
+    let indent_len = line.find(|ch: char| ch != ' ' && ch != '\t').unwrap_or(line.len());
+    &line[..indent_len]
+}
+
+/// Split the given line into runs of a single color, given the highlighted byte ranges.
+///
+/// Text outside of the highlighted ranges uses the `default` color. Ranges that overlap a previous
+/// range or do not lie on `char` boundaries are ignored.
+fn line_runs(line: &str,
+             mut highlighted: Vec<(std::ops::Range<usize>, Color)>,
+             default: Color) -> Vec<(std::ops::Range<usize>, Color)>
+{
+    highlighted.sort_by_key(|&(ref range, _)| range.start);
+    let mut runs = Vec::with_capacity(highlighted.len() * 2 + 1);
+    let mut end = 0;
+    for (range, color) in highlighted {
+        let valid = end <= range.start && range.start < range.end && range.end <= line.len()
+            && line.is_char_boundary(range.start) && line.is_char_boundary(range.end);
+        if !valid {
+            continue;
+        }
+        if end < range.start {
+            runs.push((end..range.start, default));
+        }
+        end = range.end;
+        runs.push((range, color));
+    }
+    if end < line.len() {
+        runs.push((end..line.len(), default));
+    }
+    runs
+}
+
+/// The area of the widget with the given `rect` that is not cropped by its scrollable parents.
+///
+/// Unlike `Ui::visible_area`, this uses the widget's `rect` for the current update.
+fn visible_rect(ui: &Ui, id: widget::Id, rect: Rect) -> Option<Rect> {
+    use graph::Walker;
+    let graph = ui.widget_graph();
+    let mut visible = rect;
+    let mut depth_parents = graph.depth_parent_recursion(id);
+    while let Some(parent) = depth_parents.next_node(graph) {
+        if let Some(container) = graph.widget(parent) {
+            if container.maybe_x_scroll_state.is_some() || container.maybe_y_scroll_state.is_some() {
+                visible = match visible.overlap(container.kid_area.rect) {
+                    Some(overlap) => overlap,
+                    None => return None,
+                };
+            }
+        }
+    }
+    Some(visible)
+}
+
+/// Replace each character of the given text with the `mask` character, if there is one.
+///
+/// Newlines are preserved so that the masked text has the same lines as the original.
+fn mask_text(text: &str, mask: Option<char>) -> std::borrow::Cow<str> {
+    match masked_text(text, mask) {
+        None => std::borrow::Cow::Borrowed(text),
+        Some(masked) => std::borrow::Cow::Owned(masked),
+    }
+}
+
+/// The given text with each character replaced by the `mask` character, or `None` if there is no
+/// `mask` and the text is shown as is.
+fn masked_text(text: &str, mask: Option<char>) -> Option<String> {
+    mask.map(|mask| text.chars().map(|ch| if ch == '\n' { ch } else { mask }).collect())
+}
+
+/// The text as it is shown, given the `masked_text` produced for it.
+fn shown_text<'a>(text: &'a str, masked: &'a Option<String>) -> &'a str {
+    match *masked {
+        Some(ref masked) => masked,
+        None => text,
+    }
+}
+
+/// Move (or copy if `copy` is `true`) the text selected by `cursor` to the `drop` position.
+///
+/// Returns the new text, the range of chars within the old text that was replaced, the text that
+/// replaced it and the range of chars covered by the moved text within the new text.
+///
+/// Returns `None` if there is no selection or if `drop` lies within the selection.
+fn move_selection(cursor: Cursor,
+                  drop: text::cursor::Index,
+                  copy: bool,
+                  text: &str,
+                  line_infos: &[text::line::Info])
+    -> Option<(String, std::ops::Range<usize>, String, std::ops::Range<usize>)>
+{
+    let selected = match selected_char_range(cursor, line_infos) {
+        Some(selected) => selected,
+        None => return None,
+    };
+    let drop_idx = match text::glyph::index_after_cursor(line_infos.iter().cloned(), drop) {
+        Some(idx) => idx,
+        None => return None,
+    };
+    if selected.start <= drop_idx && drop_idx <= selected.end {
+        return None;
+    }
+
+    let selected_text: String = text.chars().skip(selected.start).take(selected.len()).collect();
+    let len = selected.len();
+
+    // The range of the old text that changes, along with the text that replaces it.
+    let (replaced, inserted, new_selection) = if copy {
+        (drop_idx..drop_idx, selected_text, drop_idx..drop_idx + len)
+    } else if drop_idx < selected.start {
+        let between: String = text.chars().skip(drop_idx).take(selected.start - drop_idx).collect();
+        let inserted = selected_text + &between;
+        (drop_idx..selected.end, inserted, drop_idx..drop_idx + len)
+    } else {
+        let between: String = text.chars().skip(selected.end).take(drop_idx - selected.end).collect();
+        let inserted = between + &selected_text;
+        (selected.start..drop_idx, inserted, drop_idx - len..drop_idx)
+    };
+
+    let new_text = text.chars().take(replaced.start)
+        .chain(inserted.chars())
+        .chain(text.chars().skip(replaced.end))
+        .collect();
+    Some((new_text, replaced, inserted, new_selection))
+}
+
+/// The first of the given matches that begins at or after the char index `idx`, wrapping around to
+/// the first match.
+fn next_match(matches: &[std::ops::Range<usize>], idx: usize) -> Option<std::ops::Range<usize>> {
+    matches.iter().find(|m| m.start >= idx).or(matches.first()).cloned()
+}
+
+/// The last of the given matches that ends at or before the char index `idx`, wrapping around to the
+/// last match.
+fn prev_match(matches: &[std::ops::Range<usize>], idx: usize) -> Option<std::ops::Range<usize>> {
+    matches.iter().rev().find(|m| m.end <= idx).or(matches.last()).cloned()
+}
+
+/// A `Cursor::Selection` covering the given range of `char` indices.
+fn selection_cursor(range: std::ops::Range<usize>, line_infos: &[text::line::Info]) -> Option<Cursor> {
+    let line_infos = line_infos.iter().cloned();
+    let start = text::cursor::index_before_char(line_infos.clone(), range.start);
+    let end = text::cursor::index_before_char(line_infos, range.end);
+    match (start, end) {
+        (Some(start), Some(end)) => Some(Cursor::Selection { start: start, end: end }),
+        _ => None,
     }
+}
+
+/// The range of `char` indices covered by the given cursor if it is a non-empty `Selection`.
+fn selected_char_range(cursor: Cursor, line_infos: &[text::line::Info]) -> Option<std::ops::Range<usize>> {
+    match cursor {
+        Cursor::Idx(_) => None,
+        Cursor::Selection { .. } => cursor_char_range(cursor, line_infos)
+            .and_then(|range| if range.start != range.end { Some(range) } else { None }),
+    }
+}
 
+/// The range of `char` indices covered by the given cursor.
+///
+/// The range is empty if the cursor is a `Cursor::Idx`.
+fn cursor_char_range(cursor: Cursor, line_infos: &[text::line::Info]) -> Option<std::ops::Range<usize>> {
+    let (start, end) = match cursor {
+        Cursor::Idx(idx) => (idx, idx),
+        Cursor::Selection { start, end } => (start, end),
+    };
+    let line_infos = line_infos.iter().cloned();
+    let start_idx = text::glyph::index_after_cursor(line_infos.clone(), start);
+    let end_idx = text::glyph::index_after_cursor(line_infos, end);
+    match (start_idx, end_idx) {
+        (Some(a), Some(b)) => Some(std::cmp::min(a, b)..std::cmp::max(a, b)),
+        _ => None,
+    }
 }
 
 
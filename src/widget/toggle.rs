@@ -1,6 +1,7 @@
 //! A button that allows for toggling boolean state.
 
 use {Color, Colorable, FontSize, Borderable, Labelable, Positionable, Scalar, Widget};
+use input;
 use position::{self, Align};
 use text;
 use widget;
@@ -143,6 +144,10 @@ impl<'a> Widget for Toggle<'a> {
         self.style.clone()
     }
 
+    fn is_focusable(&self) -> bool {
+        true
+    }
+
     /// Update the state of the Toggle.
     fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
         let widget::UpdateArgs { id, state, style, rect, ui, .. } = args;
@@ -152,7 +157,11 @@ impl<'a> Widget for Toggle<'a> {
             state: value,
             count: if enabled {
                 let input = ui.widget_input(id);
-                (input.clicks().left().count() + input.taps().count()) as u16
+                // While focused, `Space` and `Return` also toggle the value.
+                let key_presses = input.presses().key()
+                    .filter(|press| press.key == input::Key::Space || press.key == input::Key::Return)
+                    .count();
+                (input.clicks().left().count() + input.taps().count() + key_presses) as u16
             } else { 0 },
         };
 
@@ -10,7 +10,9 @@ use {
     Scalar,
     Widget,
 };
-use num::Float;
+use event;
+use input;
+use num::{Float, NumCast};
 use text;
 use utils::{map_range, val_to_string};
 use widget;
@@ -74,6 +76,8 @@ widget_ids! {
 /// The state of the XYPad.
 pub struct State {
     ids: Ids,
+    /// The value at the time of the last left press, restored if the drag is cancelled.
+    value_before_drag: Option<(f64, f64)>,
 }
 
 
@@ -116,6 +120,7 @@ impl<'a, X, Y> Widget for XYPad<'a, X, Y>
     fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
         State {
             ids: Ids::new(id_gen),
+            value_before_drag: None,
         }
     }
 
@@ -151,6 +156,33 @@ impl<'a, X, Y> Widget for XYPad<'a, X, Y>
             }
         }
 
+        // Restore the value from before the drag if the drag is cancelled via `Escape`.
+        let mut value_before_drag = state.value_before_drag;
+        for widget_event in ui.widget_input(id).events() {
+            match widget_event {
+                event::Widget::Press(press) => {
+                    if let Some(input::MouseButton::Left) = press.mouse().map(|m| m.button) {
+                        value_before_drag = x.to_f64().and_then(|x| y.to_f64().map(|y| (x, y)));
+                    }
+                },
+                event::Widget::Release(release) => {
+                    if let Some(input::MouseButton::Left) = release.mouse().map(|m| m.button) {
+                        value_before_drag = None;
+                    }
+                },
+                event::Widget::DragCancelled(cancelled) if cancelled.button == input::MouseButton::Left => {
+                    if let Some((before_x, before_y)) = value_before_drag.take() {
+                        new_x = NumCast::from(before_x).unwrap();
+                        new_y = NumCast::from(before_y).unwrap();
+                    }
+                },
+                _ => (),
+            }
+        }
+        if state.value_before_drag != value_before_drag {
+            state.update(|state| state.value_before_drag = value_before_drag);
+        }
+
         // If the value across either axis has changed, produce an event.
         let event = if x != new_x || y != new_y {
             Some((new_x, new_y))